@@ -0,0 +1,7 @@
+/// Returns true if and only if every byte in `bytes` is ASCII.
+///
+/// This is used as a cheap fast path before falling back to full Unicode
+/// aware processing (e.g. case conversion).
+pub(crate) fn is_ascii(bytes: &[u8]) -> bool {
+    bytes.iter().all(u8::is_ascii)
+}
@@ -0,0 +1,672 @@
+use core::fmt;
+use core::ops;
+use core::slice;
+
+use ext_slice::ByteSlice;
+use search::{Find, FindReverse};
+use slice_index::SliceIndex;
+use utf8::{CharIndices, Chars, Utf8Chunks};
+
+#[cfg(feature = "std")]
+use bstring::BString;
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+#[cfg(feature = "std")]
+use std::ffi::OsStr;
+#[cfg(feature = "std")]
+use std::path::Path;
+#[cfg(feature = "std")]
+use utf8::Utf8Error;
+
+/// A short hand constructor for building a `&BStr`.
+///
+/// This is analogous to `str`'s `"..."` literal syntax, except it works for
+/// any type that cheaply converts to a `&[u8]`.
+#[allow(non_snake_case)]
+pub fn B<'a, B: ?Sized + AsRef<[u8]>>(bytes: &'a B) -> &'a BStr {
+    BStr::new(bytes)
+}
+
+/// A byte string slice, akin to `str` but without the guarantee of being
+/// valid UTF-8.
+///
+/// `BStr` is a wrapper around `[u8]` and provides no additional invariants
+/// over what `[u8]` already provides. It exists solely to attach a string
+/// oriented API to a byte slice.
+#[repr(transparent)]
+pub struct BStr {
+    bytes: [u8],
+}
+
+impl BStr {
+    /// Create a new `&BStr` from any type that cheaply converts to `&[u8]`.
+    pub fn new<B: ?Sized + AsRef<[u8]>>(bytes: &B) -> &BStr {
+        BStr::from_bytes(bytes.as_ref())
+    }
+
+    #[inline]
+    pub(crate) fn from_bytes(slice: &[u8]) -> &BStr {
+        unsafe { &*(slice as *const [u8] as *const BStr) }
+    }
+
+    #[inline]
+    pub(crate) fn from_bytes_mut(slice: &mut [u8]) -> &mut BStr {
+        unsafe { &mut *(slice as *mut [u8] as *mut BStr) }
+    }
+
+    /// Return this byte string's underlying bytes as a `&[u8]`.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    #[inline]
+    pub(crate) fn as_bytes_mut(&mut self) -> &mut [u8] {
+        &mut self.bytes
+    }
+
+    /// Return the number of bytes in this byte string.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Return true if and only if this byte string is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Return an immutable reference to a subslice, or `None` if the index
+    /// is out of bounds. No check is made that the index falls on a char
+    /// boundary.
+    #[inline]
+    pub fn get<I: SliceIndex<BStr>>(&self, index: I) -> Option<&I::Output> {
+        index.get(self)
+    }
+
+    /// Return the first byte offset matching the given needle.
+    ///
+    /// This, and the rest of `BStr`'s string-oriented methods, are thin
+    /// forwarders onto [`ByteSlice`](trait.ByteSlice.html), which is also
+    /// implemented directly for `[u8]`. Prefer calling through `ByteSlice`
+    /// when working with a bare byte slice instead of wrapping it first.
+    pub fn find<B: AsRef<[u8]>>(&self, needle: B) -> Option<usize> {
+        ByteSlice::find(self, needle)
+    }
+
+    /// Return the last byte offset matching the given needle.
+    pub fn rfind<B: AsRef<[u8]>>(&self, needle: B) -> Option<usize> {
+        ByteSlice::rfind(self, needle)
+    }
+
+    /// Return an iterator of the non-overlapping occurrences of `needle`.
+    pub fn find_iter<'a, 'n, B: ?Sized + AsRef<[u8]>>(
+        &'a self,
+        needle: &'n B,
+    ) -> Find<'a, 'n> {
+        ByteSlice::find_iter(self, needle)
+    }
+
+    /// Return an iterator of the non-overlapping occurrences of `needle`,
+    /// searching from the end of the haystack towards the start.
+    pub fn rfind_iter<'a, 'n, B: ?Sized + AsRef<[u8]>>(
+        &'a self,
+        needle: &'n B,
+    ) -> FindReverse<'a, 'n> {
+        ByteSlice::rfind_iter(self, needle)
+    }
+
+    /// Return the first position of any byte in `set`, or `None` if `set`
+    /// is empty or no byte of the haystack belongs to `set`.
+    pub fn find_byteset<B: AsRef<[u8]>>(&self, set: B) -> Option<usize> {
+        ByteSlice::find_byteset(self, set)
+    }
+
+    /// Return the first position of a byte *not* in `set`.
+    ///
+    /// If `set` is empty, this returns `Some(0)` for any non-empty byte
+    /// string, since every byte trivially doesn't belong to the empty set.
+    pub fn find_not_byteset<B: AsRef<[u8]>>(&self, set: B) -> Option<usize> {
+        ByteSlice::find_not_byteset(self, set)
+    }
+
+    /// Return the last position of any byte in `set`, or `None` if `set` is
+    /// empty or no byte of the haystack belongs to `set`.
+    pub fn rfind_byteset<B: AsRef<[u8]>>(&self, set: B) -> Option<usize> {
+        ByteSlice::rfind_byteset(self, set)
+    }
+
+    /// Return the last position of a byte *not* in `set`.
+    pub fn rfind_not_byteset<B: AsRef<[u8]>>(&self, set: B) -> Option<usize> {
+        ByteSlice::rfind_not_byteset(self, set)
+    }
+
+    /// Return true if and only if this byte string contains the given
+    /// needle.
+    ///
+    /// This keeps its original name (rather than `ByteSlice`'s
+    /// `contains_str`) since `BStr`, unlike bare `[u8]`, has no inherent
+    /// `contains` method of its own to collide with.
+    pub fn contains<B: AsRef<[u8]>>(&self, needle: B) -> bool {
+        ByteSlice::contains_str(self, needle)
+    }
+
+    /// Return true if and only if this byte string starts with the given
+    /// prefix.
+    pub fn starts_with<B: AsRef<[u8]>>(&self, prefix: B) -> bool {
+        ByteSlice::starts_with_str(self, prefix)
+    }
+
+    /// Return true if and only if this byte string ends with the given
+    /// suffix.
+    pub fn ends_with<B: AsRef<[u8]>>(&self, suffix: B) -> bool {
+        ByteSlice::ends_with_str(self, suffix)
+    }
+
+    /// Trim leading and trailing ASCII whitespace from this byte string.
+    pub fn trim(&self) -> &BStr {
+        ByteSlice::trim(self)
+    }
+
+    /// Trim leading ASCII whitespace from this byte string.
+    pub fn trim_start(&self) -> &BStr {
+        ByteSlice::trim_start(self)
+    }
+
+    /// Trim trailing ASCII whitespace from this byte string.
+    pub fn trim_end(&self) -> &BStr {
+        ByteSlice::trim_end(self)
+    }
+
+    /// Return an iterator over the lines in this byte string, with line
+    /// terminators stripped.
+    pub fn lines(&self) -> Lines {
+        ByteSlice::lines(self)
+    }
+
+    /// Return an iterator over the lines in this byte string, with line
+    /// terminators included.
+    pub fn lines_with_terminator(&self) -> LinesWithTerminator {
+        ByteSlice::lines_with_terminator(self)
+    }
+
+    /// Return an iterator over the whitespace-separated fields in this byte
+    /// string.
+    pub fn fields(&self) -> Fields {
+        ByteSlice::fields(self)
+    }
+
+    /// Like [`fields`](#method.fields), but splits according to a
+    /// caller-provided predicate instead of ASCII whitespace.
+    pub fn fields_with<F: FnMut(char) -> bool>(&self, f: F) -> FieldsWith<F> {
+        ByteSlice::fields_with(self, f)
+    }
+
+    /// Return an iterator over the substring-delimited pieces of this byte
+    /// string.
+    pub fn split_str<'a, 'n, B: ?Sized + AsRef<[u8]>>(&'a self, needle: &'n B) -> Split<'a, 'n> {
+        ByteSlice::split_str(self, needle)
+    }
+
+    /// Like [`split_str`](#method.split_str), but yields pieces from the
+    /// end.
+    pub fn rsplit_str<'a, 'n, B: ?Sized + AsRef<[u8]>>(
+        &'a self,
+        needle: &'n B,
+    ) -> SplitReverse<'a, 'n> {
+        ByteSlice::rsplit_str(self, needle)
+    }
+
+    /// Like [`split_str`](#method.split_str), but stops after at most `n`
+    /// pieces.
+    pub fn splitn_str<'a, 'n, B: ?Sized + AsRef<[u8]>>(
+        &'a self,
+        n: usize,
+        needle: &'n B,
+    ) -> SplitN<'a, 'n> {
+        ByteSlice::splitn_str(self, n, needle)
+    }
+
+    /// Like [`rsplit_str`](#method.rsplit_str), but stops after at most `n`
+    /// pieces.
+    pub fn rsplitn_str<'a, 'n, B: ?Sized + AsRef<[u8]>>(
+        &'a self,
+        n: usize,
+        needle: &'n B,
+    ) -> SplitNReverse<'a, 'n> {
+        ByteSlice::rsplitn_str(self, n, needle)
+    }
+
+    /// Return an iterator over the Unicode codepoints in this byte string.
+    ///
+    /// Invalid UTF-8 is substituted with `U+FFFD`.
+    pub fn chars(&self) -> Chars {
+        ByteSlice::chars(self)
+    }
+
+    /// Like [`chars`](#method.chars), but also yields the byte range of
+    /// each codepoint.
+    pub fn char_indices(&self) -> CharIndices {
+        ByteSlice::char_indices(self)
+    }
+
+    /// Return an iterator over lossless chunks of valid UTF-8 followed by
+    /// the invalid bytes that immediately follow, so that concatenating
+    /// `valid()` then `invalid()` of every chunk reconstructs the original
+    /// bytes exactly. Use this instead of [`chars`](#method.chars) when
+    /// invalid UTF-8 must be preserved rather than substituted.
+    pub fn utf8_chunks(&self) -> Utf8Chunks {
+        ByteSlice::utf8_chunks(self)
+    }
+
+    /// Replace all non-overlapping occurrences of `needle` with `replacement`.
+    #[cfg(feature = "std")]
+    pub fn replace<N: AsRef<[u8]>, R: AsRef<[u8]>>(
+        &self,
+        needle: N,
+        replacement: R,
+    ) -> BString {
+        ByteSlice::replace(self, needle, replacement)
+    }
+
+    /// Return the uppercase equivalent of this byte string, preserving any
+    /// invalid UTF-8 bytes exactly as-is.
+    #[cfg(feature = "std")]
+    pub fn to_uppercase(&self) -> BString {
+        ByteSlice::to_uppercase(self)
+    }
+
+    /// Return the lowercase equivalent of this byte string, preserving any
+    /// invalid UTF-8 bytes exactly as-is.
+    #[cfg(feature = "std")]
+    pub fn to_lowercase(&self) -> BString {
+        ByteSlice::to_lowercase(self)
+    }
+
+    /// Convert this byte string to a `&OsStr`.
+    ///
+    /// On Unix, this is zero cost and always succeeds, since `OsStr` is
+    /// already just a wrapper around arbitrary bytes there. On other
+    /// platforms (chiefly Windows), `OsStr`'s internal representation is
+    /// private and can't be borrowed from arbitrary bytes without
+    /// allocating, so this instead requires the bytes to be valid UTF-8 and
+    /// fails otherwise. Use [`to_os_str_lossy`](#method.to_os_str_lossy) to
+    /// handle non-UTF-8 bytes (including ones that encode a Windows
+    /// surrogate) without failing.
+    #[cfg(feature = "std")]
+    pub fn to_os_str(&self) -> Result<&OsStr, Utf8Error> {
+        ByteSlice::to_os_str(self)
+    }
+
+    /// Like [`to_os_str`](#method.to_os_str), but never fails.
+    ///
+    /// On Unix, this is equivalent to `to_os_str`. On other platforms,
+    /// bytes that are valid [WTF-8](../wtf8/index.html) as a whole (which
+    /// includes all valid UTF-8, plus encodings of lone Windows surrogates)
+    /// round-trip exactly. Otherwise, the bytes are converted as by
+    /// `String::from_utf8_lossy`, so every sequence that isn't valid UTF-8,
+    /// including an encoded surrogate, is substituted with the Unicode
+    /// replacement codepoint.
+    #[cfg(feature = "std")]
+    pub fn to_os_str_lossy(&self) -> Cow<OsStr> {
+        ByteSlice::to_os_str_lossy(self)
+    }
+
+    /// Convert this byte string to a `&Path`.
+    ///
+    /// See [`to_os_str`](#method.to_os_str) for details on when this can
+    /// fail.
+    #[cfg(feature = "std")]
+    pub fn to_path(&self) -> Result<&Path, Utf8Error> {
+        ByteSlice::to_path(self)
+    }
+
+    /// Like [`to_path`](#method.to_path), but never fails.
+    ///
+    /// See [`to_os_str_lossy`](#method.to_os_str_lossy) for details on how
+    /// non-UTF-8 bytes are handled.
+    #[cfg(feature = "std")]
+    pub fn to_path_lossy(&self) -> Cow<Path> {
+        ByteSlice::to_path_lossy(self)
+    }
+}
+
+impl ops::Deref for BStr {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl<I: SliceIndex<BStr>> ops::Index<I> for BStr {
+    type Output = I::Output;
+
+    #[inline]
+    fn index(&self, index: I) -> &I::Output {
+        index.index(self)
+    }
+}
+
+impl<I: SliceIndex<BStr>> ops::IndexMut<I> for BStr {
+    #[inline]
+    fn index_mut(&mut self, index: I) -> &mut I::Output {
+        index.index_mut(self)
+    }
+}
+
+impl fmt::Debug for BStr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\"")?;
+        for (_, _, ch) in self.char_indices() {
+            for c in ch.escape_debug() {
+                write!(f, "{}", c)?;
+            }
+        }
+        write!(f, "\"")
+    }
+}
+
+impl PartialEq for BStr {
+    #[inline]
+    fn eq(&self, other: &BStr) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl Eq for BStr {}
+
+/// An iterator over the bytes in a byte string.
+#[derive(Clone, Debug)]
+pub struct Bytes<'a> {
+    it: slice::Iter<'a, u8>,
+}
+
+impl<'a> Bytes<'a> {
+    pub(crate) fn new(bs: &'a BStr) -> Bytes<'a> {
+        Bytes { it: bs.as_bytes().iter() }
+    }
+}
+
+impl<'a> Iterator for Bytes<'a> {
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<u8> {
+        self.it.next().copied()
+    }
+}
+
+/// An iterator over the lines of a byte string, with terminators stripped.
+#[derive(Clone, Debug)]
+pub struct Lines<'a> {
+    it: LinesWithTerminator<'a>,
+}
+
+impl<'a> Lines<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Lines<'a> {
+        Lines { it: LinesWithTerminator::new(bytes) }
+    }
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = &'a BStr;
+
+    fn next(&mut self) -> Option<&'a BStr> {
+        let line = self.it.next()?;
+        Some(BStr::from_bytes(trim_line_terminator(line.as_bytes())))
+    }
+}
+
+#[inline]
+fn trim_line_terminator(line: &[u8]) -> &[u8] {
+    if line.last() == Some(&b'\n') {
+        let line = &line[..line.len() - 1];
+        if line.last() == Some(&b'\r') {
+            return &line[..line.len() - 1];
+        }
+        return line;
+    }
+    line
+}
+
+/// An iterator over the lines of a byte string, with terminators included.
+#[derive(Clone, Debug)]
+pub struct LinesWithTerminator<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> LinesWithTerminator<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> LinesWithTerminator<'a> {
+        LinesWithTerminator { bytes }
+    }
+}
+
+impl<'a> Iterator for LinesWithTerminator<'a> {
+    type Item = &'a BStr;
+
+    fn next(&mut self) -> Option<&'a BStr> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+        let end = match self.bytes.iter().position(|&b| b == b'\n') {
+            None => self.bytes.len(),
+            Some(i) => i + 1,
+        };
+        let (line, rest) = self.bytes.split_at(end);
+        self.bytes = rest;
+        Some(BStr::from_bytes(line))
+    }
+}
+
+/// An iterator over whitespace-separated fields of a byte string.
+#[derive(Clone, Debug)]
+pub struct Fields<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Fields<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Fields<'a> {
+        Fields { bytes }
+    }
+}
+
+impl<'a> Iterator for Fields<'a> {
+    type Item = &'a BStr;
+
+    fn next(&mut self) -> Option<&'a BStr> {
+        let is_ws = |b: u8| b" \t\r\n\x0B\x0C".contains(&b);
+        let start = self.bytes.iter().position(|&b| !is_ws(b))?;
+        let bytes = &self.bytes[start..];
+        let end = bytes.iter().position(|&b| is_ws(b)).unwrap_or(bytes.len());
+        self.bytes = &bytes[end..];
+        Some(BStr::from_bytes(&bytes[..end]))
+    }
+}
+
+/// Like [`Fields`](struct.Fields.html), but splits according to a
+/// caller-provided predicate instead of ASCII whitespace.
+#[derive(Clone)]
+pub struct FieldsWith<'a, F> {
+    bytes: &'a [u8],
+    f: F,
+}
+
+impl<'a, F: FnMut(char) -> bool> FieldsWith<'a, F> {
+    pub(crate) fn new(bytes: &'a [u8], f: F) -> FieldsWith<'a, F> {
+        FieldsWith { bytes, f }
+    }
+}
+
+impl<'a, F: FnMut(char) -> bool> Iterator for FieldsWith<'a, F> {
+    type Item = &'a BStr;
+
+    fn next(&mut self) -> Option<&'a BStr> {
+        let f = &mut self.f;
+        let mut chars = CharIndices::new(self.bytes);
+        let (start, _) = loop {
+            let (s, e, ch) = chars.next()?;
+            if !f(ch) {
+                break (s, e);
+            }
+        };
+        let mut end = self.bytes.len();
+        for (s, _, ch) in CharIndices::new(&self.bytes[start..]) {
+            if f(ch) {
+                end = start + s;
+                break;
+            }
+        }
+        let field = &self.bytes[start..end];
+        self.bytes = &self.bytes[end..];
+        Some(BStr::from_bytes(field))
+    }
+}
+
+/// An iterator over substring-delimited pieces of a byte string.
+#[derive(Debug)]
+pub struct Split<'a, 'n> {
+    finder: Find<'a, 'n>,
+    bytes: &'a [u8],
+    last: usize,
+    done: bool,
+}
+
+impl<'a, 'n> Split<'a, 'n> {
+    pub(crate) fn new(bytes: &'a [u8], needle: &'n [u8]) -> Split<'a, 'n> {
+        Split { finder: Find::new(bytes, needle), bytes, last: 0, done: false }
+    }
+}
+
+impl<'a, 'n> Iterator for Split<'a, 'n> {
+    type Item = &'a BStr;
+
+    fn next(&mut self) -> Option<&'a BStr> {
+        if self.done {
+            return None;
+        }
+        match self.finder.next() {
+            Some(start) => {
+                let needle_len = self.finder.needle_len();
+                let piece = &self.bytes[self.last..start];
+                self.last = start + needle_len;
+                Some(BStr::from_bytes(piece))
+            }
+            None => {
+                self.done = true;
+                Some(BStr::from_bytes(&self.bytes[self.last..]))
+            }
+        }
+    }
+}
+
+/// Like [`Split`](struct.Split.html), but yields pieces from the end.
+#[derive(Debug)]
+pub struct SplitReverse<'a, 'n> {
+    finder: FindReverse<'a, 'n>,
+    bytes: &'a [u8],
+    last: usize,
+    done: bool,
+}
+
+impl<'a, 'n> SplitReverse<'a, 'n> {
+    pub(crate) fn new(bytes: &'a [u8], needle: &'n [u8]) -> SplitReverse<'a, 'n> {
+        SplitReverse { finder: FindReverse::new(bytes, needle), bytes, last: bytes.len(), done: false }
+    }
+}
+
+impl<'a, 'n> Iterator for SplitReverse<'a, 'n> {
+    type Item = &'a BStr;
+
+    fn next(&mut self) -> Option<&'a BStr> {
+        if self.done {
+            return None;
+        }
+        match self.finder.next() {
+            Some(start) => {
+                let needle_len = self.finder.needle_len();
+                let piece = &self.bytes[start + needle_len..self.last];
+                self.last = start;
+                Some(BStr::from_bytes(piece))
+            }
+            None => {
+                self.done = true;
+                Some(BStr::from_bytes(&self.bytes[..self.last]))
+            }
+        }
+    }
+}
+
+/// Like [`Split`](struct.Split.html), but stops after at most `n` pieces.
+#[derive(Debug)]
+pub struct SplitN<'a, 'n> {
+    split: Split<'a, 'n>,
+    limit: usize,
+    bytes: &'a [u8],
+    count: usize,
+}
+
+impl<'a, 'n> SplitN<'a, 'n> {
+    pub(crate) fn new(bytes: &'a [u8], needle: &'n [u8], limit: usize) -> SplitN<'a, 'n> {
+        SplitN { split: Split::new(bytes, needle), limit, bytes, count: 0 }
+    }
+}
+
+impl<'a, 'n> Iterator for SplitN<'a, 'n> {
+    type Item = &'a BStr;
+
+    fn next(&mut self) -> Option<&'a BStr> {
+        if self.split.done {
+            return None;
+        }
+        if self.count + 1 == self.limit {
+            self.count += 1;
+            let rest = &self.bytes[self.split.last..];
+            self.split.done = true;
+            return Some(BStr::from_bytes(rest));
+        }
+        if self.count >= self.limit {
+            return None;
+        }
+        self.count += 1;
+        self.split.next()
+    }
+}
+
+/// Like [`SplitN`](struct.SplitN.html), but yields pieces from the end.
+#[derive(Debug)]
+pub struct SplitNReverse<'a, 'n> {
+    split: SplitReverse<'a, 'n>,
+    limit: usize,
+    bytes: &'a [u8],
+    count: usize,
+}
+
+impl<'a, 'n> SplitNReverse<'a, 'n> {
+    pub(crate) fn new(bytes: &'a [u8], needle: &'n [u8], limit: usize) -> SplitNReverse<'a, 'n> {
+        SplitNReverse { split: SplitReverse::new(bytes, needle), limit, bytes, count: 0 }
+    }
+}
+
+impl<'a, 'n> Iterator for SplitNReverse<'a, 'n> {
+    type Item = &'a BStr;
+
+    fn next(&mut self) -> Option<&'a BStr> {
+        if self.split.done {
+            return None;
+        }
+        if self.count + 1 == self.limit {
+            self.count += 1;
+            let rest = &self.bytes[..self.split.last];
+            self.split.done = true;
+            return Some(BStr::from_bytes(rest));
+        }
+        if self.count >= self.limit {
+            return None;
+        }
+        self.count += 1;
+        self.split.next()
+    }
+}
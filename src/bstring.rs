@@ -0,0 +1,265 @@
+use std::borrow::{Borrow, BorrowMut};
+use std::ffi::OsString;
+use std::fmt;
+use std::ops;
+use std::path::PathBuf;
+use std::vec;
+
+use bstr::BStr;
+use ext_vec::ByteVec;
+use os_str;
+use slice_index::SliceIndex;
+
+/// An owned, growable byte string, akin to `String` but without the
+/// guarantee of being valid UTF-8.
+#[derive(Clone, Default, Eq, PartialEq)]
+pub struct BString {
+    bytes: Vec<u8>,
+}
+
+impl BString {
+    /// Create a new empty `BString`.
+    pub fn new() -> BString {
+        BString { bytes: Vec::new() }
+    }
+
+    /// Create a new empty `BString` with the given capacity.
+    pub fn with_capacity(capacity: usize) -> BString {
+        BString { bytes: Vec::with_capacity(capacity) }
+    }
+
+    /// Return this byte string's contents as a byte slice.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    #[inline]
+    pub(crate) fn as_vec(&self) -> &Vec<u8> {
+        &self.bytes
+    }
+
+    #[inline]
+    pub(crate) fn as_vec_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.bytes
+    }
+
+    /// View this owned byte string as a borrowed `&BStr`.
+    pub fn as_bstr(&self) -> &BStr {
+        BStr::from_bytes(&self.bytes)
+    }
+
+    /// Push the given bytes onto the end of this byte string.
+    ///
+    /// This, and the rest of `BString`'s owning mutation methods, are thin
+    /// forwarders onto [`ByteVec`](trait.ByteVec.html), which is also
+    /// implemented directly for `Vec<u8>`.
+    pub fn push<B: AsRef<[u8]>>(&mut self, bytes: B) {
+        ByteVec::push_str(self, bytes)
+    }
+
+    /// Push a single byte onto the end of this byte string.
+    pub fn push_byte(&mut self, byte: u8) {
+        ByteVec::push_byte(self, byte)
+    }
+
+    /// Consume this byte string, returning its underlying bytes.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// Convert this byte string into a `String`, if and only if it is valid
+    /// UTF-8, returning the original `BString` on failure.
+    pub fn into_string(self) -> Result<String, FromUtf8Error> {
+        ByteVec::into_string(self)
+    }
+
+    /// Return the number of bytes in this byte string.
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Return true if and only if this byte string is empty.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Remove and return every byte in this byte string, leaving it empty.
+    pub fn drain_bytes(&mut self) -> DrainBytes {
+        ByteVec::drain_bytes(self)
+    }
+
+    /// Losslessly convert an `OsString` into a `BString`.
+    ///
+    /// This always succeeds. On Unix, it's zero cost, since `OsString` is
+    /// already just a wrapper around arbitrary bytes there. On other
+    /// platforms (chiefly Windows), it goes through a
+    /// [WTF-8](../wtf8/index.html) re-encode of the `OsString`'s UTF-16
+    /// code units, which, unlike a UTF-8 check, has no failure case: every
+    /// sequence of UTF-16 code units (including ones with unpaired
+    /// surrogates) has a WTF-8 encoding.
+    pub fn from_os_string(os_string: OsString) -> Result<BString, OsString> {
+        os_str::from_os_string(os_string)
+    }
+
+    /// Losslessly convert this `BString` into an `OsString`.
+    ///
+    /// This, like the rest of `BString`'s owning mutation methods, is a
+    /// thin forwarder onto [`ByteVec`](trait.ByteVec.html), which is also
+    /// implemented directly for `Vec<u8>`.
+    ///
+    /// This fails if and only if the bytes aren't valid (on Unix, this
+    /// never happens; on other platforms, this means the bytes aren't even
+    /// valid [WTF-8](../wtf8/index.html), which is a strictly larger set
+    /// than valid UTF-8).
+    pub fn into_os_string(self) -> Result<OsString, BString> {
+        ByteVec::into_os_string(self)
+    }
+
+    /// Losslessly convert a `PathBuf` into a `BString`.
+    ///
+    /// See [`from_os_string`](#method.from_os_string) for details on when
+    /// this can fail.
+    pub fn from_path_buf(path: PathBuf) -> Result<BString, PathBuf> {
+        os_str::from_path_buf(path)
+    }
+
+    /// Losslessly convert this `BString` into a `PathBuf`.
+    ///
+    /// See [`into_os_string`](#method.into_os_string) for details on when
+    /// this can fail.
+    pub fn into_path_buf(self) -> Result<PathBuf, BString> {
+        ByteVec::into_path_buf(self)
+    }
+}
+
+impl ops::Deref for BString {
+    type Target = BStr;
+
+    fn deref(&self) -> &BStr {
+        self.as_bstr()
+    }
+}
+
+impl ops::DerefMut for BString {
+    fn deref_mut(&mut self) -> &mut BStr {
+        BStr::from_bytes_mut(&mut self.bytes)
+    }
+}
+
+impl<I: SliceIndex<BStr>> ops::Index<I> for BString {
+    type Output = I::Output;
+
+    fn index(&self, index: I) -> &I::Output {
+        index.index(self.as_bstr())
+    }
+}
+
+impl Borrow<BStr> for BString {
+    fn borrow(&self) -> &BStr {
+        self.as_bstr()
+    }
+}
+
+impl BorrowMut<BStr> for BString {
+    fn borrow_mut(&mut self) -> &mut BStr {
+        BStr::from_bytes_mut(&mut self.bytes)
+    }
+}
+
+impl From<Vec<u8>> for BString {
+    fn from(bytes: Vec<u8>) -> BString {
+        BString { bytes }
+    }
+}
+
+impl From<String> for BString {
+    fn from(s: String) -> BString {
+        BString { bytes: s.into_bytes() }
+    }
+}
+
+impl<'a> From<&'a str> for BString {
+    fn from(s: &'a str) -> BString {
+        BString { bytes: s.as_bytes().to_vec() }
+    }
+}
+
+impl<'a> From<&'a [u8]> for BString {
+    fn from(bytes: &'a [u8]) -> BString {
+        BString { bytes: bytes.to_vec() }
+    }
+}
+
+impl<'a> From<&'a BStr> for BString {
+    fn from(bstr: &'a BStr) -> BString {
+        BString { bytes: bstr.as_bytes().to_vec() }
+    }
+}
+
+impl fmt::Debug for BString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_bstr(), f)
+    }
+}
+
+/// An error that occurs when converting a `BString` into a `String` fails
+/// because the bytes are not valid UTF-8.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FromUtf8Error {
+    pub(crate) bytes: BString,
+}
+
+impl FromUtf8Error {
+    /// Return the original bytes that failed to convert to a `String`.
+    pub fn into_bstring(self) -> BString {
+        self.bytes
+    }
+}
+
+impl fmt::Display for FromUtf8Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid UTF-8 found while converting to a String")
+    }
+}
+
+impl std::error::Error for FromUtf8Error {}
+
+/// An iterator over the bytes drained out of a `BString`.
+#[derive(Debug)]
+pub struct DrainBytes<'a> {
+    pub(crate) it: vec::Drain<'a, u8>,
+}
+
+impl<'a> Iterator for DrainBytes<'a> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        self.it.next()
+    }
+}
+
+/// Concatenate the given byte strings together into a single `BString`.
+pub fn concat<T: AsRef<[u8]>, I: IntoIterator<Item = T>>(elements: I) -> BString {
+    let mut dest = BString::new();
+    for element in elements {
+        dest.push(element.as_ref());
+    }
+    dest
+}
+
+/// Join the given byte strings together with `separator` in between each
+/// pair, producing a single `BString`.
+pub fn join<S: AsRef<[u8]>, T: AsRef<[u8]>, I: IntoIterator<Item = T>>(
+    separator: S,
+    elements: I,
+) -> BString {
+    let separator = separator.as_ref();
+    let mut dest = BString::new();
+    for (i, element) in elements.into_iter().enumerate() {
+        if i > 0 {
+            dest.push(separator);
+        }
+        dest.push(element.as_ref());
+    }
+    dest
+}
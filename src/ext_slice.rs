@@ -0,0 +1,408 @@
+//! The [`ByteSlice`](trait.ByteSlice.html) extension trait, which puts the
+//! entire string-oriented API directly onto `[u8]`.
+//!
+//! `BStr` still exists as a convenient named type, but it's now a thin
+//! `repr(transparent)` wrapper whose inherent methods simply forward to
+//! this trait. This means the same methods are available on a bare byte
+//! slice, without requiring callers to wrap it first:
+//!
+//! ```
+//! use bstr::ByteSlice;
+//!
+//! assert_eq!(b"foo bar foo".find_iter("foo").collect::<Vec<_>>(), vec![0, 8]);
+//! ```
+
+#[cfg(feature = "std")]
+use ascii;
+use bstr::{
+    BStr, Bytes, Fields, FieldsWith, Lines, LinesWithTerminator, Split, SplitN, SplitNReverse,
+    SplitReverse,
+};
+use search::{self, Find, FindReverse, Finder, FinderReverse};
+use utf8::{self, CharIndices, Chars, Utf8Chunks};
+#[cfg(feature = "unicode")]
+use unicode::{
+    GraphemeIndices, Graphemes, SentenceIndices, Sentences, WordIndices, Words,
+    WordsWithBreakIndices, WordsWithBreaks,
+};
+
+#[cfg(feature = "std")]
+use bstring::BString;
+#[cfg(feature = "std")]
+use os_str;
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+#[cfg(feature = "std")]
+use std::ffi::OsStr;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for [u8] {}
+    impl Sealed for super::BStr {}
+}
+
+/// An extension trait that puts the entire byte string API directly onto
+/// `[u8]` (and, through `BStr`'s `Deref<Target = [u8]>`, onto `BStr` too).
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait ByteSlice: private::Sealed {
+    /// View this value as a byte slice.
+    fn as_bytes(&self) -> &[u8];
+
+    /// View this value as a byte string slice.
+    #[inline]
+    fn as_bstr(&self) -> &BStr {
+        BStr::from_bytes(self.as_bytes())
+    }
+
+    /// Return the first byte offset matching the given needle.
+    fn find<B: AsRef<[u8]>>(&self, needle: B) -> Option<usize> {
+        Finder::new(&needle).find(self.as_bytes())
+    }
+
+    /// Return the last byte offset matching the given needle.
+    fn rfind<B: AsRef<[u8]>>(&self, needle: B) -> Option<usize> {
+        FinderReverse::new(&needle).rfind(self.as_bytes())
+    }
+
+    /// Return an iterator of the non-overlapping occurrences of `needle`.
+    fn find_iter<'a, 'n, B: ?Sized + AsRef<[u8]>>(&'a self, needle: &'n B) -> Find<'a, 'n> {
+        Find::new(self.as_bytes(), needle.as_ref())
+    }
+
+    /// Return an iterator of the non-overlapping occurrences of `needle`,
+    /// searching from the end of the haystack towards the start.
+    fn rfind_iter<'a, 'n, B: ?Sized + AsRef<[u8]>>(
+        &'a self,
+        needle: &'n B,
+    ) -> FindReverse<'a, 'n> {
+        FindReverse::new(self.as_bytes(), needle.as_ref())
+    }
+
+    /// Return the first position of any byte in `set`, or `None` if `set`
+    /// is empty or no byte of the haystack belongs to `set`.
+    fn find_byteset<B: AsRef<[u8]>>(&self, set: B) -> Option<usize> {
+        search::find_byteset(self.as_bytes(), set.as_ref())
+    }
+
+    /// Return the first position of a byte *not* in `set`.
+    ///
+    /// If `set` is empty, this returns `Some(0)` for any non-empty byte
+    /// string, since every byte trivially doesn't belong to the empty set.
+    fn find_not_byteset<B: AsRef<[u8]>>(&self, set: B) -> Option<usize> {
+        search::find_not_byteset(self.as_bytes(), set.as_ref())
+    }
+
+    /// Return the last position of any byte in `set`, or `None` if `set` is
+    /// empty or no byte of the haystack belongs to `set`.
+    fn rfind_byteset<B: AsRef<[u8]>>(&self, set: B) -> Option<usize> {
+        search::rfind_byteset(self.as_bytes(), set.as_ref())
+    }
+
+    /// Return the last position of a byte *not* in `set`.
+    fn rfind_not_byteset<B: AsRef<[u8]>>(&self, set: B) -> Option<usize> {
+        search::rfind_not_byteset(self.as_bytes(), set.as_ref())
+    }
+
+    /// Return true if and only if this byte string contains the given
+    /// needle.
+    fn contains_str<B: AsRef<[u8]>>(&self, needle: B) -> bool {
+        self.find(needle).is_some()
+    }
+
+    /// Return true if and only if this byte string starts with the given
+    /// prefix.
+    fn starts_with_str<B: AsRef<[u8]>>(&self, prefix: B) -> bool {
+        self.as_bytes().starts_with(prefix.as_ref())
+    }
+
+    /// Return true if and only if this byte string ends with the given
+    /// suffix.
+    fn ends_with_str<B: AsRef<[u8]>>(&self, suffix: B) -> bool {
+        self.as_bytes().ends_with(suffix.as_ref())
+    }
+
+    /// Trim leading and trailing ASCII whitespace from this byte string.
+    fn trim(&self) -> &BStr {
+        self.trim_start().trim_end()
+    }
+
+    /// Trim leading ASCII whitespace from this byte string.
+    fn trim_start(&self) -> &BStr {
+        let bytes = self.as_bytes();
+        let i = search::find_not_byteset(bytes, b" \t\r\n\x0B\x0C").unwrap_or(bytes.len());
+        BStr::from_bytes(&bytes[i..])
+    }
+
+    /// Trim trailing ASCII whitespace from this byte string.
+    fn trim_end(&self) -> &BStr {
+        let bytes = self.as_bytes();
+        let i = search::rfind_not_byteset(bytes, b" \t\r\n\x0B\x0C")
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        BStr::from_bytes(&bytes[..i])
+    }
+
+    /// Return an iterator over the lines in this byte string, with line
+    /// terminators stripped.
+    fn lines(&self) -> Lines {
+        Lines::new(self.as_bytes())
+    }
+
+    /// Return an iterator over the lines in this byte string, with line
+    /// terminators included.
+    fn lines_with_terminator(&self) -> LinesWithTerminator {
+        LinesWithTerminator::new(self.as_bytes())
+    }
+
+    /// Return an iterator over the whitespace-separated fields in this byte
+    /// string.
+    fn fields(&self) -> Fields {
+        Fields::new(self.as_bytes())
+    }
+
+    /// Like [`fields`](#method.fields), but splits according to a
+    /// caller-provided predicate instead of ASCII whitespace.
+    fn fields_with<F: FnMut(char) -> bool>(&self, f: F) -> FieldsWith<F> {
+        FieldsWith::new(self.as_bytes(), f)
+    }
+
+    /// Return an iterator over the substring-delimited pieces of this byte
+    /// string.
+    fn split_str<'a, 'n, B: ?Sized + AsRef<[u8]>>(&'a self, needle: &'n B) -> Split<'a, 'n> {
+        Split::new(self.as_bytes(), needle.as_ref())
+    }
+
+    /// Like [`split_str`](#method.split_str), but yields pieces from the
+    /// end.
+    fn rsplit_str<'a, 'n, B: ?Sized + AsRef<[u8]>>(
+        &'a self,
+        needle: &'n B,
+    ) -> SplitReverse<'a, 'n> {
+        SplitReverse::new(self.as_bytes(), needle.as_ref())
+    }
+
+    /// Like [`split_str`](#method.split_str), but stops after at most `n`
+    /// pieces.
+    fn splitn_str<'a, 'n, B: ?Sized + AsRef<[u8]>>(
+        &'a self,
+        n: usize,
+        needle: &'n B,
+    ) -> SplitN<'a, 'n> {
+        SplitN::new(self.as_bytes(), needle.as_ref(), n)
+    }
+
+    /// Like [`rsplit_str`](#method.rsplit_str), but stops after at most `n`
+    /// pieces.
+    fn rsplitn_str<'a, 'n, B: ?Sized + AsRef<[u8]>>(
+        &'a self,
+        n: usize,
+        needle: &'n B,
+    ) -> SplitNReverse<'a, 'n> {
+        SplitNReverse::new(self.as_bytes(), needle.as_ref(), n)
+    }
+
+    /// Return an iterator over the Unicode codepoints in this byte string.
+    ///
+    /// Invalid UTF-8 is substituted with `U+FFFD`.
+    fn chars(&self) -> Chars {
+        Chars::new(self.as_bytes())
+    }
+
+    /// Like [`chars`](#method.chars), but also yields the byte range of
+    /// each codepoint.
+    fn char_indices(&self) -> CharIndices {
+        CharIndices::new(self.as_bytes())
+    }
+
+    /// Return an iterator over the bytes in this byte string.
+    fn bytes(&self) -> Bytes {
+        Bytes::new(self.as_bstr())
+    }
+
+    /// Return an iterator over lossless chunks of valid UTF-8 followed by
+    /// the invalid bytes that immediately follow, so that concatenating
+    /// `valid()` then `invalid()` of every chunk reconstructs the original
+    /// bytes exactly. Use this instead of [`chars`](#method.chars) when
+    /// invalid UTF-8 must be preserved rather than substituted.
+    fn utf8_chunks(&self) -> Utf8Chunks {
+        Utf8Chunks::new(self.as_bytes())
+    }
+
+    /// Return an iterator over the grapheme clusters in this byte string.
+    #[cfg(feature = "unicode")]
+    fn graphemes(&self) -> Graphemes {
+        Graphemes::new(self.as_bytes())
+    }
+
+    /// Like [`graphemes`](#method.graphemes), but also yields the byte
+    /// range of each grapheme cluster.
+    #[cfg(feature = "unicode")]
+    fn grapheme_indices(&self) -> GraphemeIndices {
+        GraphemeIndices::new(self.as_bytes())
+    }
+
+    /// Return an iterator over the words in this byte string.
+    #[cfg(feature = "unicode")]
+    fn words(&self) -> Words {
+        Words::new(self.as_bytes())
+    }
+
+    /// Like [`words`](#method.words), but also yields the byte range of
+    /// each word.
+    #[cfg(feature = "unicode")]
+    fn word_indices(&self) -> WordIndices {
+        WordIndices::new(self.as_bytes())
+    }
+
+    /// Like [`words`](#method.words), but also yields non-word runs.
+    #[cfg(feature = "unicode")]
+    fn words_with_breaks(&self) -> WordsWithBreaks {
+        WordsWithBreaks::new(self.as_bytes())
+    }
+
+    /// Like [`words_with_breaks`](#method.words_with_breaks), but also
+    /// yields the byte range of each run.
+    #[cfg(feature = "unicode")]
+    fn words_with_break_indices(&self) -> WordsWithBreakIndices {
+        WordsWithBreakIndices::new(self.as_bytes())
+    }
+
+    /// Return an iterator over the sentences in this byte string.
+    #[cfg(feature = "unicode")]
+    fn sentences(&self) -> Sentences {
+        Sentences::new(self.as_bytes())
+    }
+
+    /// Like [`sentences`](#method.sentences), but also yields the byte
+    /// range of each sentence.
+    #[cfg(feature = "unicode")]
+    fn sentence_indices(&self) -> SentenceIndices {
+        SentenceIndices::new(self.as_bytes())
+    }
+
+    /// Replace all non-overlapping occurrences of `needle` with
+    /// `replacement`.
+    #[cfg(feature = "std")]
+    fn replace<N: AsRef<[u8]>, R: AsRef<[u8]>>(&self, needle: N, replacement: R) -> BString {
+        let bytes = self.as_bytes();
+        let (needle, replacement) = (needle.as_ref(), replacement.as_ref());
+        let mut dest = BString::new();
+        let mut last = 0;
+        for start in bytes.as_bstr().find_iter(needle) {
+            dest.push(&bytes[last..start]);
+            dest.push(replacement);
+            last = start + needle.len();
+        }
+        dest.push(&bytes[last..]);
+        dest
+    }
+
+    /// Return the uppercase equivalent of this byte string, preserving any
+    /// invalid UTF-8 bytes exactly as-is.
+    #[cfg(feature = "std")]
+    fn to_uppercase(&self) -> BString {
+        map_chars(self.as_bytes(), char::to_uppercase)
+    }
+
+    /// Return the lowercase equivalent of this byte string, preserving any
+    /// invalid UTF-8 bytes exactly as-is.
+    #[cfg(feature = "std")]
+    fn to_lowercase(&self) -> BString {
+        map_chars(self.as_bytes(), char::to_lowercase)
+    }
+
+    /// Convert this byte string to a `&OsStr`.
+    ///
+    /// On Unix, this is zero cost and always succeeds, since `OsStr` is
+    /// already just a wrapper around arbitrary bytes there. On other
+    /// platforms (chiefly Windows), `OsStr`'s internal representation is
+    /// private and can't be borrowed from arbitrary bytes without
+    /// allocating, so this instead requires the bytes to be valid UTF-8 and
+    /// fails otherwise. Use [`to_os_str_lossy`](#method.to_os_str_lossy) to
+    /// handle non-UTF-8 bytes (including ones that encode a Windows
+    /// surrogate) without failing.
+    #[cfg(feature = "std")]
+    fn to_os_str(&self) -> Result<&OsStr, utf8::Utf8Error> {
+        os_str::to_os_str(self.as_bytes())
+    }
+
+    /// Like [`to_os_str`](#method.to_os_str), but never fails.
+    ///
+    /// On Unix, this is equivalent to `to_os_str`. On other platforms,
+    /// bytes that are valid [WTF-8](../wtf8/index.html) as a whole (which
+    /// includes all valid UTF-8, plus encodings of lone Windows surrogates)
+    /// round-trip exactly. Otherwise, the bytes are converted as by
+    /// `String::from_utf8_lossy`, so every sequence that isn't valid UTF-8,
+    /// including an encoded surrogate, is substituted with the Unicode
+    /// replacement codepoint.
+    #[cfg(feature = "std")]
+    fn to_os_str_lossy(&self) -> Cow<OsStr> {
+        os_str::to_os_str_lossy(self.as_bytes())
+    }
+
+    /// Convert this byte string to a `&Path`.
+    ///
+    /// See [`to_os_str`](#method.to_os_str) for details on when this can
+    /// fail.
+    #[cfg(feature = "std")]
+    fn to_path(&self) -> Result<&Path, utf8::Utf8Error> {
+        os_str::to_path(self.as_bytes())
+    }
+
+    /// Like [`to_path`](#method.to_path), but never fails.
+    ///
+    /// See [`to_os_str_lossy`](#method.to_os_str_lossy) for details on how
+    /// non-UTF-8 bytes are handled.
+    #[cfg(feature = "std")]
+    fn to_path_lossy(&self) -> Cow<Path> {
+        os_str::to_path_lossy(self.as_bytes())
+    }
+}
+
+impl ByteSlice for [u8] {
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        self
+    }
+}
+
+impl ByteSlice for BStr {
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        BStr::as_bytes(self)
+    }
+}
+
+#[cfg(feature = "std")]
+fn map_chars<I: Iterator<Item = char>>(bytes: &[u8], mut f: impl FnMut(char) -> I) -> BString {
+    use std::fmt::Write as _;
+
+    // ASCII-only byte strings never need the char-by-char path below.
+    if ascii::is_ascii(bytes) {
+        let mut dest = BString::with_capacity(bytes.len());
+        for &b in bytes {
+            for mapped in f(b as char) {
+                dest.push_byte(mapped as u8);
+            }
+        }
+        return dest;
+    }
+
+    let mut dest = BString::new();
+    for (start, end, ch) in CharIndices::new(bytes) {
+        if ch == utf8::REPLACEMENT && &bytes[start..end] != "\u{FFFD}".as_bytes() {
+            dest.push(&bytes[start..end]);
+        } else {
+            let mut buf = String::new();
+            for mapped in f(ch) {
+                let _ = buf.write_char(mapped);
+            }
+            dest.push(buf.as_bytes());
+        }
+    }
+    dest
+}
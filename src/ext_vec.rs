@@ -0,0 +1,136 @@
+//! The [`ByteVec`](trait.ByteVec.html) extension trait, which puts the
+//! entire owned byte string API directly onto `Vec<u8>`.
+//!
+//! `BString` still exists as a convenient named type, but it's now a thin
+//! wrapper whose inherent methods simply forward to this trait.
+
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+use bstr::BStr;
+use bstring::{BString, DrainBytes, FromUtf8Error};
+use os_str;
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for Vec<u8> {}
+    impl Sealed for super::BString {}
+}
+
+/// An extension trait that puts the entire owned byte string API directly
+/// onto `Vec<u8>` (and onto `BString` too).
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait ByteVec: private::Sealed {
+    /// View this value as its underlying byte vector.
+    fn as_vec(&self) -> &Vec<u8>;
+
+    /// View this value as a mutable reference to its underlying byte
+    /// vector.
+    fn as_vec_mut(&mut self) -> &mut Vec<u8>;
+
+    /// Consume this value, returning its underlying byte vector.
+    fn into_vec(self) -> Vec<u8>;
+
+    /// View this value as a byte string slice.
+    #[inline]
+    fn as_bstr(&self) -> &BStr {
+        BStr::from_bytes(self.as_vec())
+    }
+
+    /// Push the given bytes onto the end of this byte string.
+    ///
+    /// This is named `push_str` rather than `push` because `Vec<u8>` already
+    /// has an inherent `push(&mut self, value: u8)` method with an
+    /// incompatible signature; since inherent methods always take priority
+    /// over trait methods of the same name, reusing `push` here would turn
+    /// every multi-byte push on a bare `Vec<u8>` into a confusing type
+    /// error instead of a call to this method.
+    fn push_str<B: AsRef<[u8]>>(&mut self, bytes: B) {
+        self.as_vec_mut().extend_from_slice(bytes.as_ref());
+    }
+
+    /// Push a single byte onto the end of this byte string.
+    fn push_byte(&mut self, byte: u8) {
+        self.as_vec_mut().push(byte);
+    }
+
+    /// Remove and return every byte in this byte string, leaving it empty.
+    fn drain_bytes(&mut self) -> DrainBytes {
+        DrainBytes { it: self.as_vec_mut().drain(..) }
+    }
+
+    /// Convert this byte string into a `String`, if and only if it is valid
+    /// UTF-8, returning the original bytes on failure.
+    fn into_string(self) -> Result<String, FromUtf8Error>
+    where
+        Self: Sized,
+    {
+        String::from_utf8(self.into_vec())
+            .map_err(|err| FromUtf8Error { bytes: BString::from(err.into_bytes()) })
+    }
+
+    /// Losslessly convert this byte string into an `OsString`.
+    ///
+    /// This fails if and only if the bytes aren't valid (on Unix, this
+    /// never happens; on other platforms, this means the bytes aren't even
+    /// valid [WTF-8](../wtf8/index.html), which is a strictly larger set
+    /// than valid UTF-8).
+    ///
+    /// There's no corresponding `from_os_string` on this trait, since that
+    /// would be a named constructor rather than a method on an existing
+    /// value; like `BStr::from_bytes`, it stays an inherent function on
+    /// `BString` (see [`BString::from_os_string`](struct.BString.html#method.from_os_string)).
+    fn into_os_string(self) -> Result<OsString, BString>
+    where
+        Self: Sized,
+    {
+        os_str::into_os_string(BString::from(self.into_vec()))
+    }
+
+    /// Losslessly convert this byte string into a `PathBuf`.
+    ///
+    /// See [`into_os_string`](#method.into_os_string) for details on when
+    /// this can fail.
+    fn into_path_buf(self) -> Result<PathBuf, BString>
+    where
+        Self: Sized,
+    {
+        self.into_os_string().map(PathBuf::from)
+    }
+}
+
+impl ByteVec for Vec<u8> {
+    #[inline]
+    fn as_vec(&self) -> &Vec<u8> {
+        self
+    }
+
+    #[inline]
+    fn as_vec_mut(&mut self) -> &mut Vec<u8> {
+        self
+    }
+
+    #[inline]
+    fn into_vec(self) -> Vec<u8> {
+        self
+    }
+}
+
+impl ByteVec for BString {
+    #[inline]
+    fn as_vec(&self) -> &Vec<u8> {
+        BString::as_vec(self)
+    }
+
+    #[inline]
+    fn as_vec_mut(&mut self) -> &mut Vec<u8> {
+        BString::as_vec_mut(self)
+    }
+
+    #[inline]
+    fn into_vec(self) -> Vec<u8> {
+        BString::into_bytes(self)
+    }
+}
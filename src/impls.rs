@@ -0,0 +1,92 @@
+//! Trait implementations gluing `BStr`/`BString` to the standard library's
+//! own string and byte slice types, so that byte strings can be compared
+//! against and built from them without an explicit conversion step.
+
+use bstr::BStr;
+#[cfg(feature = "std")]
+use bstring::BString;
+
+macro_rules! impl_partial_eq {
+    ($lhs:ty, $rhs:ty) => {
+        impl<'a> PartialEq<$rhs> for $lhs {
+            #[inline]
+            fn eq(&self, other: &$rhs) -> bool {
+                self.as_bytes() == AsRef::<[u8]>::as_ref(other)
+            }
+        }
+
+        impl<'a> PartialEq<$lhs> for $rhs {
+            #[inline]
+            fn eq(&self, other: &$lhs) -> bool {
+                AsRef::<[u8]>::as_ref(self) == other.as_bytes()
+            }
+        }
+    };
+}
+
+impl_partial_eq!(BStr, [u8]);
+impl_partial_eq!(BStr, str);
+impl_partial_eq!(&'a BStr, [u8]);
+impl_partial_eq!(&'a BStr, str);
+
+#[cfg(feature = "std")]
+impl_partial_eq!(BString, [u8]);
+#[cfg(feature = "std")]
+impl_partial_eq!(BString, str);
+#[cfg(feature = "std")]
+impl_partial_eq!(BString, Vec<u8>);
+#[cfg(feature = "std")]
+impl_partial_eq!(BString, String);
+#[cfg(feature = "std")]
+impl_partial_eq!(BString, BStr);
+
+impl<'a> From<&'a [u8]> for &'a BStr {
+    #[inline]
+    fn from(bytes: &'a [u8]) -> &'a BStr {
+        BStr::from_bytes(bytes)
+    }
+}
+
+impl<'a> From<&'a str> for &'a BStr {
+    #[inline]
+    fn from(s: &'a str) -> &'a BStr {
+        BStr::from_bytes(s.as_bytes())
+    }
+}
+
+impl<'a> AsRef<[u8]> for BStr {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<'a> AsRef<BStr> for BStr {
+    #[inline]
+    fn as_ref(&self) -> &BStr {
+        self
+    }
+}
+
+impl<'a> AsRef<[u8]> for &'a BStr {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        BStr::as_bytes(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl AsRef<[u8]> for BString {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+#[cfg(feature = "std")]
+impl AsRef<BStr> for BString {
+    #[inline]
+    fn as_ref(&self) -> &BStr {
+        self.as_bstr()
+    }
+}
@@ -0,0 +1,23 @@
+//! Byte-string oriented extensions to `std::io`.
+
+use std::io::{self, BufRead};
+
+use bstring::BString;
+
+/// An extension trait for `BufRead` that reads lines as byte strings
+/// instead of requiring them to be valid UTF-8.
+pub trait BufReadExt: BufRead {
+    /// Read all bytes up to and including the next `\n` (or until EOF) into
+    /// `buf`, returning the number of bytes read.
+    ///
+    /// Unlike [`BufRead::read_line`], this does not require the line to be
+    /// valid UTF-8.
+    fn read_bstring_line(&mut self, buf: &mut BString) -> io::Result<usize> {
+        let mut bytes = Vec::new();
+        let n = self.read_until(b'\n', &mut bytes)?;
+        buf.push(&bytes);
+        Ok(n)
+    }
+}
+
+impl<B: BufRead> BufReadExt for B {}
@@ -250,13 +250,16 @@ extern crate ucd_parse;
 pub use bstr::{
     B, BStr,
     Bytes,
-    Finder, FinderReverse, Find, FindReverse,
     Split, SplitReverse, SplitN, SplitNReverse,
     Fields, FieldsWith,
     Lines, LinesWithTerminator,
 };
 #[cfg(feature = "std")]
 pub use bstring::{BString, DrainBytes, FromUtf8Error, concat, join};
+pub use ext_slice::ByteSlice;
+#[cfg(feature = "std")]
+pub use ext_vec::ByteVec;
+pub use search::{Find, FindReverse, Finder, FinderReverse};
 pub use slice_index::SliceIndex;
 #[cfg(feature = "unicode")]
 pub use unicode::{
@@ -266,6 +269,7 @@ pub use unicode::{
 };
 pub use utf8::{
     Utf8Error, Chars, CharIndices,
+    Utf8Chunk, Utf8Chunks,
     decode as decode_utf8,
     decode_last as decode_last_utf8,
 };
@@ -274,10 +278,14 @@ mod ascii;
 mod bstr;
 #[cfg(feature = "std")]
 mod bstring;
-mod cow;
+mod ext_slice;
+#[cfg(feature = "std")]
+mod ext_vec;
 mod impls;
 #[cfg(feature = "std")]
 pub mod io;
+#[cfg(feature = "std")]
+mod os_str;
 mod search;
 mod slice_index;
 #[cfg(test)]
@@ -285,6 +293,8 @@ mod tests;
 #[cfg(feature = "unicode")]
 mod unicode;
 mod utf8;
+#[cfg(all(feature = "std", not(unix)))]
+mod wtf8;
 
 #[cfg(test)]
 mod apitests {
@@ -0,0 +1,107 @@
+//! Platform-correct conversions between byte strings and the platform's own
+//! `OsStr`/`OsString`/`Path`/`PathBuf` types.
+//!
+//! On Unix, these conversions are zero cost, via
+//! `std::os::unix::ffi::OsStrExt`/`OsStringExt`.
+//!
+//! Elsewhere (chiefly Windows), `OsString` is backed by UTF-16 code units
+//! that are allowed to contain unpaired surrogates, which isn't
+//! representable in `str`. The *owning* conversions (`from_os_string`,
+//! `into_os_string`, and the lossy `to_os_str_lossy`/`to_path_lossy`) go
+//! through the [`wtf8`](../wtf8/index.html) codec, so such surrogates still
+//! round-trip losslessly rather than being replaced or rejected. The
+//! zero-copy, borrowing conversions (`to_os_str`, `to_path`) can't use that
+//! codec — `OsStr`'s internal representation is private, and std exposes no
+//! way to borrow its bytes directly on this platform — so those still
+//! require the input to be strict UTF-8.
+
+use std::borrow::Cow;
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+
+use bstring::BString;
+use utf8::Utf8Error;
+#[cfg(not(unix))]
+use wtf8;
+
+#[cfg(unix)]
+pub(crate) fn to_os_str(bytes: &[u8]) -> Result<&OsStr, Utf8Error> {
+    use std::os::unix::ffi::OsStrExt;
+    Ok(OsStr::from_bytes(bytes))
+}
+
+/// Requires strict UTF-8 on this platform: see the module docs for why the
+/// WTF-8 codec used elsewhere in this module can't help with a borrowing
+/// conversion like this one.
+#[cfg(not(unix))]
+pub(crate) fn to_os_str(bytes: &[u8]) -> Result<&OsStr, Utf8Error> {
+    core::str::from_utf8(bytes).map(OsStr::new).map_err(Utf8Error::from_std)
+}
+
+#[cfg(unix)]
+pub(crate) fn to_os_str_lossy(bytes: &[u8]) -> Cow<OsStr> {
+    use std::os::unix::ffi::OsStrExt;
+    Cow::Borrowed(OsStr::from_bytes(bytes))
+}
+
+#[cfg(not(unix))]
+pub(crate) fn to_os_str_lossy(bytes: &[u8]) -> Cow<OsStr> {
+    if let Ok(s) = core::str::from_utf8(bytes) {
+        return Cow::Borrowed(OsStr::new(s));
+    }
+    // Not strict UTF-8, but may still be valid WTF-8 (e.g. bytes that came
+    // from a Windows path containing an unpaired surrogate); decode that
+    // losslessly rather than falling straight to byte-for-byte replacement.
+    use std::os::windows::ffi::OsStringExt;
+    match wtf8::decode_wide(bytes) {
+        Ok(wide) => Cow::Owned(OsString::from_wide(&wide)),
+        Err(_) => Cow::Owned(OsString::from(String::from_utf8_lossy(bytes).into_owned())),
+    }
+}
+
+#[cfg(unix)]
+pub(crate) fn from_os_string(os_string: OsString) -> Result<BString, OsString> {
+    use std::os::unix::ffi::OsStringExt;
+    Ok(BString::from(os_string.into_vec()))
+}
+
+/// Always succeeds: every sequence of UTF-16 code units, including those
+/// with unpaired surrogates, has a WTF-8 encoding.
+#[cfg(not(unix))]
+pub(crate) fn from_os_string(os_string: OsString) -> Result<BString, OsString> {
+    use std::os::windows::ffi::OsStrExt;
+    let wide: Vec<u16> = os_string.encode_wide().collect();
+    Ok(BString::from(wtf8::encode_wide(&wide)))
+}
+
+#[cfg(unix)]
+pub(crate) fn into_os_string(bytes: BString) -> Result<OsString, BString> {
+    use std::os::unix::ffi::OsStringExt;
+    Ok(OsString::from_vec(bytes.into_bytes()))
+}
+
+/// Fails only when the bytes aren't even valid WTF-8 (as opposed to merely
+/// invalid UTF-8).
+#[cfg(not(unix))]
+pub(crate) fn into_os_string(bytes: BString) -> Result<OsString, BString> {
+    use std::os::windows::ffi::OsStringExt;
+    match wtf8::decode_wide(bytes.as_bytes()) {
+        Ok(wide) => Ok(OsString::from_wide(&wide)),
+        Err(_) => Err(bytes),
+    }
+}
+
+pub(crate) fn to_path(bytes: &[u8]) -> Result<&Path, Utf8Error> {
+    to_os_str(bytes).map(Path::new)
+}
+
+pub(crate) fn to_path_lossy(bytes: &[u8]) -> Cow<Path> {
+    match to_os_str_lossy(bytes) {
+        Cow::Borrowed(os_str) => Cow::Borrowed(Path::new(os_str)),
+        Cow::Owned(os_string) => Cow::Owned(PathBuf::from(os_string)),
+    }
+}
+
+pub(crate) fn from_path_buf(path: PathBuf) -> Result<BString, PathBuf> {
+    from_os_string(path.into_os_string()).map_err(PathBuf::from)
+}
@@ -0,0 +1,241 @@
+use memchr::{memchr, memrchr};
+
+/// A single substring searcher.
+///
+/// This searches for one particular needle in any given haystack. It can be
+/// reused across multiple searches.
+#[derive(Clone, Debug)]
+pub struct Finder<'n> {
+    needle: &'n [u8],
+}
+
+impl<'n> Finder<'n> {
+    /// Create a new finder for the given needle.
+    pub fn new<B: ?Sized + AsRef<[u8]>>(needle: &'n B) -> Finder<'n> {
+        Finder { needle: needle.as_ref() }
+    }
+
+    /// Return the needle used by this finder.
+    pub fn needle(&self) -> &[u8] {
+        self.needle
+    }
+
+    /// Find the first occurrence of this needle in the given haystack.
+    pub fn find(&self, haystack: &[u8]) -> Option<usize> {
+        if self.needle.is_empty() {
+            return Some(0);
+        }
+        if self.needle.len() > haystack.len() {
+            return None;
+        }
+        let first = self.needle[0];
+        let last_start = haystack.len() - self.needle.len();
+        let mut i = 0;
+        while i <= last_start {
+            match memchr(first, &haystack[i..=last_start]) {
+                None => return None,
+                Some(pos) => {
+                    let start = i + pos;
+                    if &haystack[start..start + self.needle.len()] == self.needle {
+                        return Some(start);
+                    }
+                    i = start + 1;
+                }
+            }
+        }
+        None
+    }
+}
+
+/// A single substring reverse searcher.
+#[derive(Clone, Debug)]
+pub struct FinderReverse<'n> {
+    needle: &'n [u8],
+}
+
+impl<'n> FinderReverse<'n> {
+    /// Create a new reverse finder for the given needle.
+    pub fn new<B: ?Sized + AsRef<[u8]>>(needle: &'n B) -> FinderReverse<'n> {
+        FinderReverse { needle: needle.as_ref() }
+    }
+
+    /// Return the needle used by this finder.
+    pub fn needle(&self) -> &[u8] {
+        self.needle
+    }
+
+    /// Find the last occurrence of this needle in the given haystack.
+    pub fn rfind(&self, haystack: &[u8]) -> Option<usize> {
+        if self.needle.is_empty() {
+            return Some(haystack.len());
+        }
+        if self.needle.len() > haystack.len() {
+            return None;
+        }
+        let last = self.needle[self.needle.len() - 1];
+        let mut end = haystack.len();
+        while end >= self.needle.len() {
+            match memrchr(last, &haystack[self.needle.len() - 1..end]) {
+                None => return None,
+                Some(pos) => {
+                    let last_pos = self.needle.len() - 1 + pos;
+                    let start = last_pos + 1 - self.needle.len();
+                    if &haystack[start..start + self.needle.len()] == self.needle {
+                        return Some(start);
+                    }
+                    end = last_pos;
+                }
+            }
+        }
+        None
+    }
+}
+
+/// An iterator over non-overlapping substring matches.
+#[derive(Debug)]
+pub struct Find<'a, 'n> {
+    haystack: &'a [u8],
+    finder: Finder<'n>,
+    pos: usize,
+}
+
+impl<'a, 'n> Find<'a, 'n> {
+    pub(crate) fn new(haystack: &'a [u8], needle: &'n [u8]) -> Find<'a, 'n> {
+        Find { haystack, finder: Finder::new(needle), pos: 0 }
+    }
+
+    pub(crate) fn needle_len(&self) -> usize {
+        self.finder.needle().len()
+    }
+}
+
+impl<'a, 'n> Iterator for Find<'a, 'n> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.pos > self.haystack.len() {
+            return None;
+        }
+        match self.finder.find(&self.haystack[self.pos..]) {
+            None => {
+                self.pos = self.haystack.len() + 1;
+                None
+            }
+            Some(i) => {
+                let abs = self.pos + i;
+                let step = self.finder.needle().len().max(1);
+                self.pos = abs + step;
+                Some(abs)
+            }
+        }
+    }
+}
+
+/// An iterator over non-overlapping substring matches in reverse.
+#[derive(Debug)]
+pub struct FindReverse<'a, 'n> {
+    haystack: &'a [u8],
+    finder: FinderReverse<'n>,
+    end: Option<usize>,
+}
+
+impl<'a, 'n> FindReverse<'a, 'n> {
+    pub(crate) fn new(haystack: &'a [u8], needle: &'n [u8]) -> FindReverse<'a, 'n> {
+        FindReverse { haystack, finder: FinderReverse::new(needle), end: Some(haystack.len()) }
+    }
+
+    pub(crate) fn needle_len(&self) -> usize {
+        self.finder.needle().len()
+    }
+}
+
+impl<'a, 'n> Iterator for FindReverse<'a, 'n> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let end = self.end?;
+        match self.finder.rfind(&self.haystack[..end]) {
+            None => {
+                self.end = None;
+                None
+            }
+            Some(start) => {
+                // An empty needle matches at `start` itself, so step past
+                // it to make progress.
+                self.end = if start == 0 {
+                    None
+                } else if self.needle_len() == 0 {
+                    Some(start - 1)
+                } else {
+                    Some(start)
+                };
+                Some(start)
+            }
+        }
+    }
+}
+
+/// A precomputed 256-entry membership table for a fixed set of bytes.
+///
+/// Building this once and reusing it turns a search for "any byte in this
+/// set" into a single table lookup per byte, which is considerably faster
+/// than repeatedly calling `find` for every byte in an alternation.
+#[derive(Clone, Copy)]
+pub struct Byteset([bool; 256]);
+
+impl Byteset {
+    /// Build a membership table from the given set of bytes.
+    pub fn new(set: &[u8]) -> Byteset {
+        let mut table = [false; 256];
+        for &b in set {
+            table[b as usize] = true;
+        }
+        Byteset(table)
+    }
+
+    /// Returns whether the given byte belongs to this set.
+    #[inline]
+    pub fn contains(&self, byte: u8) -> bool {
+        self.0[byte as usize]
+    }
+}
+
+/// Find the first occurrence of any byte in `set` within `haystack`.
+///
+/// Returns `None` whenever `set` is empty, since there is nothing to match.
+pub fn find_byteset(haystack: &[u8], set: &[u8]) -> Option<usize> {
+    if set.is_empty() {
+        return None;
+    }
+    let table = Byteset::new(set);
+    haystack.iter().position(|&b| table.contains(b))
+}
+
+/// Find the last occurrence of any byte in `set` within `haystack`.
+///
+/// Returns `None` whenever `set` is empty, since there is nothing to match.
+pub fn rfind_byteset(haystack: &[u8], set: &[u8]) -> Option<usize> {
+    if set.is_empty() {
+        return None;
+    }
+    let table = Byteset::new(set);
+    haystack.iter().rposition(|&b| table.contains(b))
+}
+
+/// Find the first occurrence of a byte *not* in `set` within `haystack`.
+///
+/// When `set` is empty, every byte qualifies, so this returns `Some(0)` for
+/// any non-empty haystack.
+pub fn find_not_byteset(haystack: &[u8], set: &[u8]) -> Option<usize> {
+    let table = Byteset::new(set);
+    haystack.iter().position(|&b| !table.contains(b))
+}
+
+/// Find the last occurrence of a byte *not* in `set` within `haystack`.
+///
+/// When `set` is empty, every byte qualifies, so this returns the index of
+/// the last byte for any non-empty haystack.
+pub fn rfind_not_byteset(haystack: &[u8], set: &[u8]) -> Option<usize> {
+    let table = Byteset::new(set);
+    haystack.iter().rposition(|&b| !table.contains(b))
+}
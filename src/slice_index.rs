@@ -0,0 +1,126 @@
+use core::ops;
+
+use bstr::BStr;
+
+mod private {
+    use super::ops;
+
+    pub trait Sealed {}
+
+    impl Sealed for ops::Range<usize> {}
+    impl Sealed for ops::RangeTo<usize> {}
+    impl Sealed for ops::RangeFrom<usize> {}
+    impl Sealed for ops::RangeFull {}
+    impl Sealed for ops::RangeInclusive<usize> {}
+    impl Sealed for ops::RangeToInclusive<usize> {}
+}
+
+/// A helper trait for forwarding indexing operations on `[u8]` to `BStr`.
+///
+/// This is analogous to the standard library's (unstable) `SliceIndex`
+/// trait, except it's scoped to the indexing operations supported by this
+/// crate. It is sealed and cannot be implemented outside of this crate.
+pub trait SliceIndex<T: ?Sized>: private::Sealed {
+    /// The output type returned by this indexing operation.
+    type Output: ?Sized;
+
+    /// Returns the output at this location, if in bounds.
+    fn get(self, slice: &T) -> Option<&Self::Output>;
+
+    /// Returns the mutable output at this location, if in bounds.
+    fn get_mut(self, slice: &mut T) -> Option<&mut Self::Output>;
+
+    /// Returns the output at this location, without bounds checking.
+    unsafe fn get_unchecked(self, slice: &T) -> &Self::Output;
+
+    /// Returns the mutable output at this location, without bounds checking.
+    unsafe fn get_unchecked_mut(self, slice: &mut T) -> &mut Self::Output;
+
+    /// Returns the output at this location, panicking if out of bounds.
+    fn index(self, slice: &T) -> &Self::Output;
+
+    /// Returns the mutable output at this location, panicking if out of
+    /// bounds.
+    fn index_mut(self, slice: &mut T) -> &mut Self::Output;
+}
+
+impl SliceIndex<BStr> for ops::Range<usize> {
+    type Output = BStr;
+
+    #[inline]
+    fn get(self, slice: &BStr) -> Option<&BStr> {
+        slice.as_bytes().get(self).map(BStr::from_bytes)
+    }
+
+    #[inline]
+    fn get_mut(self, slice: &mut BStr) -> Option<&mut BStr> {
+        slice.as_bytes_mut().get_mut(self).map(BStr::from_bytes_mut)
+    }
+
+    #[inline]
+    unsafe fn get_unchecked(self, slice: &BStr) -> &BStr {
+        BStr::from_bytes(slice.as_bytes().get_unchecked(self))
+    }
+
+    #[inline]
+    unsafe fn get_unchecked_mut(self, slice: &mut BStr) -> &mut BStr {
+        BStr::from_bytes_mut(slice.as_bytes_mut().get_unchecked_mut(self))
+    }
+
+    #[inline]
+    fn index(self, slice: &BStr) -> &BStr {
+        BStr::from_bytes(&slice.as_bytes()[self])
+    }
+
+    #[inline]
+    fn index_mut(self, slice: &mut BStr) -> &mut BStr {
+        BStr::from_bytes_mut(&mut slice.as_bytes_mut()[self])
+    }
+}
+
+macro_rules! impl_range_via_range {
+    ($ty:ty) => {
+        impl SliceIndex<BStr> for $ty {
+            type Output = BStr;
+
+            #[inline]
+            fn get(self, slice: &BStr) -> Option<&BStr> {
+                slice.as_bytes().get(self).map(BStr::from_bytes)
+            }
+
+            #[inline]
+            fn get_mut(self, slice: &mut BStr) -> Option<&mut BStr> {
+                slice
+                    .as_bytes_mut()
+                    .get_mut(self)
+                    .map(BStr::from_bytes_mut)
+            }
+
+            #[inline]
+            unsafe fn get_unchecked(self, slice: &BStr) -> &BStr {
+                BStr::from_bytes(slice.as_bytes().get_unchecked(self))
+            }
+
+            #[inline]
+            unsafe fn get_unchecked_mut(self, slice: &mut BStr) -> &mut BStr {
+                BStr::from_bytes_mut(slice.as_bytes_mut().get_unchecked_mut(self))
+            }
+
+            #[inline]
+            fn index(self, slice: &BStr) -> &BStr {
+                BStr::from_bytes(&slice.as_bytes()[self])
+            }
+
+            #[inline]
+            fn index_mut(self, slice: &mut BStr) -> &mut BStr {
+                BStr::from_bytes_mut(&mut slice.as_bytes_mut()[self])
+            }
+        }
+    };
+}
+
+impl_range_via_range!(ops::RangeTo<usize>);
+impl_range_via_range!(ops::RangeFrom<usize>);
+impl_range_via_range!(ops::RangeFull);
+impl_range_via_range!(ops::RangeInclusive<usize>);
+impl_range_via_range!(ops::RangeToInclusive<usize>);
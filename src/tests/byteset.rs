@@ -0,0 +1,41 @@
+use B;
+
+#[test]
+fn find_byteset_basic() {
+    assert_eq!(B("foo bar baz").find_byteset(" "), Some(3));
+    assert_eq!(B("foo bar baz").find_byteset("xyz"), Some(10));
+    assert_eq!(B("foobarbaz").find_byteset("xyz"), Some(8));
+    assert_eq!(B("foobarbaz").find_byteset("q"), None);
+}
+
+#[test]
+fn find_byteset_empty_set() {
+    assert_eq!(B("foo").find_byteset(""), None);
+    assert_eq!(B("").find_byteset("abc"), None);
+}
+
+#[test]
+fn find_not_byteset_basic() {
+    assert_eq!(B("   foo").find_not_byteset(" "), Some(3));
+    assert_eq!(B("foo").find_not_byteset(""), Some(0));
+    assert_eq!(B("   ").find_not_byteset(" "), None);
+}
+
+#[test]
+fn rfind_byteset_basic() {
+    assert_eq!(B("foo bar baz").rfind_byteset(" "), Some(7));
+    assert_eq!(B("foobarbaz").rfind_byteset("q"), None);
+}
+
+#[test]
+fn rfind_byteset_empty_set() {
+    assert_eq!(B("foo").rfind_byteset(""), None);
+    assert_eq!(B("").rfind_byteset("abc"), None);
+}
+
+#[test]
+fn rfind_not_byteset_basic() {
+    assert_eq!(B("foo   ").rfind_not_byteset(" "), Some(2));
+    assert_eq!(B("foo").rfind_not_byteset(""), Some(2));
+    assert_eq!(B("   ").rfind_not_byteset(" "), None);
+}
@@ -0,0 +1,52 @@
+use ByteSlice;
+
+#[test]
+fn find_iter_on_bare_slice() {
+    assert_eq!(b"foo bar foo".find_iter("foo").collect::<Vec<_>>(), vec![0, 8]);
+}
+
+#[test]
+fn contains_str_does_not_collide_with_inherent_contains() {
+    assert!(b"foo bar".contains_str("bar"));
+    assert!(!b"foo bar".contains_str("baz"));
+}
+
+#[test]
+fn starts_ends_with_str() {
+    assert!(b"foo bar".starts_with_str("foo"));
+    assert!(b"foo bar".ends_with_str("bar"));
+    assert!(!b"foo bar".starts_with_str("bar"));
+}
+
+#[test]
+fn trim_on_bare_slice() {
+    assert_eq!(b"  foo  ".trim(), "foo".as_bytes());
+}
+
+#[test]
+fn lines_on_bare_slice() {
+    let lines: Vec<&[u8]> = b"foo\nbar\n".lines().map(|l| l.as_bytes()).collect();
+    assert_eq!(lines, vec![b"foo".as_ref(), b"bar".as_ref()]);
+}
+
+#[test]
+fn bstr_and_slice_agree() {
+    use B;
+
+    let s = B("foo bar foo");
+    assert_eq!(s.find_iter("foo").collect::<Vec<_>>(), b"foo bar foo".find_iter("foo").collect::<Vec<_>>());
+}
+
+#[test]
+fn bstr_keeps_original_method_names() {
+    use B;
+
+    // BStr has no inherent `contains`/`starts_with`/`ends_with` of its own
+    // to collide with (unlike bare `[u8]`), so it keeps these names rather
+    // than `ByteSlice`'s `contains_str`/`starts_with_str`/`ends_with_str`.
+    let s = B("foo bar");
+    assert!(s.contains("bar"));
+    assert!(!s.contains("baz"));
+    assert!(s.starts_with("foo"));
+    assert!(s.ends_with("bar"));
+}
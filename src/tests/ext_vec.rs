@@ -0,0 +1,26 @@
+use ByteVec;
+
+#[test]
+fn push_on_bare_vec() {
+    let mut v: Vec<u8> = Vec::from("foo".as_bytes());
+    v.push_str("bar");
+    v.push_byte(b'!');
+    assert_eq!(v, b"foobar!".to_vec());
+}
+
+#[test]
+fn into_string_on_bare_vec() {
+    let v: Vec<u8> = b"foo".to_vec();
+    assert_eq!(v.into_string().unwrap(), "foo");
+
+    let v: Vec<u8> = b"\xFF".to_vec();
+    assert!(v.into_string().is_err());
+}
+
+#[test]
+fn drain_bytes_on_bare_vec() {
+    let mut v: Vec<u8> = b"foo".to_vec();
+    let drained: Vec<u8> = v.drain_bytes().collect();
+    assert_eq!(drained, b"foo".to_vec());
+    assert!(v.is_empty());
+}
@@ -0,0 +1,7 @@
+mod byteset;
+mod ext_slice;
+#[cfg(feature = "std")]
+mod ext_vec;
+#[cfg(feature = "std")]
+mod os_str;
+mod utf8_chunks;
@@ -0,0 +1,104 @@
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+use {BString, ByteSlice, ByteVec, B};
+
+#[test]
+fn to_os_str_roundtrip() {
+    let bs = B("foo/bar.txt");
+    let os_str = bs.to_os_str().unwrap();
+    assert_eq!(os_str, OsString::from("foo/bar.txt"));
+}
+
+#[test]
+fn to_path_roundtrip() {
+    let bs = B("foo/bar.txt");
+    let path = bs.to_path().unwrap();
+    assert_eq!(path, PathBuf::from("foo/bar.txt"));
+}
+
+#[test]
+fn os_string_roundtrip() {
+    let original = OsString::from("foo/bar.txt");
+    let bstring = BString::from_os_string(original.clone()).unwrap();
+    assert_eq!(bstring.clone().into_os_string().unwrap(), original);
+}
+
+#[test]
+fn path_buf_roundtrip() {
+    let original = PathBuf::from("foo/bar.txt");
+    let bstring = BString::from_path_buf(original.clone()).unwrap();
+    assert_eq!(bstring.clone().into_path_buf().unwrap(), original);
+}
+
+#[test]
+fn to_os_str_and_into_os_string_on_bare_types() {
+    let bytes: &[u8] = b"foo/bar.txt";
+    let os_str = bytes.to_os_str().unwrap();
+    assert_eq!(os_str, OsString::from("foo/bar.txt"));
+
+    let v: Vec<u8> = b"foo/bar.txt".to_vec();
+    let os_string = v.into_os_string().unwrap();
+    assert_eq!(os_string, OsString::from("foo/bar.txt"));
+}
+
+#[cfg(unix)]
+#[test]
+fn invalid_utf8_is_zero_cost_on_unix() {
+    use std::os::unix::ffi::OsStrExt;
+
+    let bs = B(b"\xFF\xFE");
+    let os_str = bs.to_os_str().unwrap();
+    assert_eq!(os_str.as_bytes(), b"\xFF\xFE");
+}
+
+#[cfg(not(unix))]
+#[test]
+fn lone_surrogate_roundtrips_through_wtf8() {
+    use wtf8;
+
+    // A lone (unpaired) UTF-16 surrogate, as can appear in a Windows
+    // `OsString` but is illegal in UTF-16 text and has no UTF-8 encoding.
+    let wide = [0xD800u16];
+    let encoded = wtf8::encode_wide(&wide);
+    assert_eq!(wtf8::decode_wide(&encoded).unwrap(), &wide[..]);
+
+    // Mixed with ordinary text and a real surrogate pair (a supplementary
+    // codepoint), the whole sequence should still round-trip exactly.
+    let wide = [
+        'f' as u16, 'o' as u16, 'o' as u16, 0xD800, 0xD83D, 0xDE00, 'z' as u16,
+    ];
+    let encoded = wtf8::encode_wide(&wide);
+    assert_eq!(wtf8::decode_wide(&encoded).unwrap(), &wide[..]);
+}
+
+#[cfg(not(unix))]
+#[test]
+fn decode_wide_rejects_overlong_and_out_of_range_encodings() {
+    use wtf8;
+
+    // Overlong encodings (a codepoint re-encoded with more bytes than
+    // necessary) must be rejected rather than silently accepted.
+    assert!(wtf8::decode_wide(b"\xC0\x80").is_err()); // overlong NUL
+    assert!(wtf8::decode_wide(b"\xE0\x80\x80").is_err()); // overlong NUL
+    assert!(wtf8::decode_wide(b"\xF0\x80\x80\x80").is_err()); // overlong NUL,
+                                                               // and would
+                                                               // underflow
+                                                               // `c - 0x10000`
+                                                               // if accepted
+
+    // A codepoint above the Unicode range (> U+10FFFF) must be rejected.
+    assert!(wtf8::decode_wide(b"\xF7\xBF\xBF\xBF").is_err());
+}
+
+#[cfg(not(unix))]
+#[test]
+fn lone_surrogate_roundtrips_through_os_string() {
+    use std::os::windows::ffi::{OsStrExt, OsStringExt};
+
+    let wide = [0xD800u16];
+    let original = OsString::from_wide(&wide);
+    let bstring = BString::from_os_string(original.clone()).unwrap();
+    let roundtripped = bstring.into_os_string().unwrap();
+    assert_eq!(roundtripped.encode_wide().collect::<Vec<u16>>(), wide);
+}
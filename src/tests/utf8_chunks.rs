@@ -0,0 +1,46 @@
+use B;
+
+fn collect(bs: &[u8]) -> Vec<(String, Vec<u8>)> {
+    B(bs)
+        .utf8_chunks()
+        .map(|chunk| (chunk.valid().to_string(), chunk.invalid().as_bytes().to_vec()))
+        .collect()
+}
+
+#[test]
+fn all_valid() {
+    assert_eq!(collect(b"hello world"), vec![("hello world".to_string(), vec![])]);
+}
+
+#[test]
+fn trailing_invalid() {
+    assert_eq!(collect(b"abc\xFF\xFF"), vec![("abc".to_string(), vec![0xFF, 0xFF])]);
+}
+
+#[test]
+fn interleaved() {
+    assert_eq!(
+        collect(b"foo\xFFbar\xE2\x98baz"),
+        vec![
+            ("foo".to_string(), vec![0xFF]),
+            ("bar".to_string(), vec![0xE2, 0x98]),
+            ("baz".to_string(), vec![]),
+        ],
+    );
+}
+
+#[test]
+fn round_trips_exactly() {
+    let original: &[u8] = b"a\xFFb\xE2\x98\x87c\xC3\x28";
+    let mut rebuilt = Vec::new();
+    for chunk in B(original).utf8_chunks() {
+        rebuilt.extend_from_slice(chunk.valid().as_bytes());
+        rebuilt.extend_from_slice(chunk.invalid().as_bytes());
+    }
+    assert_eq!(rebuilt, original);
+}
+
+#[test]
+fn empty() {
+    assert_eq!(collect(b""), vec![]);
+}
@@ -0,0 +1,221 @@
+//! Unicode segmentation over byte strings: graphemes, words and sentences.
+//!
+//! These iterators operate on the codepoints of a byte string (substituting
+//! invalid UTF-8 with `U+FFFD`, just like [`Chars`](../struct.Chars.html))
+//! and approximate the boundaries defined by Unicode's text segmentation
+//! annex (UAX #29).
+
+use bstr::BStr;
+use utf8::CharIndices;
+
+fn is_word_byte(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
+/// An iterator over the grapheme clusters of a byte string.
+#[derive(Clone, Debug)]
+pub struct Graphemes<'a> {
+    it: GraphemeIndices<'a>,
+}
+
+impl<'a> Graphemes<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Graphemes<'a> {
+        Graphemes { it: GraphemeIndices::new(bytes) }
+    }
+}
+
+impl<'a> Iterator for Graphemes<'a> {
+    type Item = &'a BStr;
+
+    fn next(&mut self) -> Option<&'a BStr> {
+        self.it.next().map(|(_, _, g)| g)
+    }
+}
+
+/// Like [`Graphemes`](struct.Graphemes.html), but also yields the byte range
+/// of each grapheme cluster.
+#[derive(Clone, Debug)]
+pub struct GraphemeIndices<'a> {
+    bytes: &'a [u8],
+    chars: CharIndices<'a>,
+}
+
+impl<'a> GraphemeIndices<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> GraphemeIndices<'a> {
+        GraphemeIndices { bytes, chars: CharIndices::new(bytes) }
+    }
+}
+
+impl<'a> Iterator for GraphemeIndices<'a> {
+    type Item = (usize, usize, &'a BStr);
+
+    fn next(&mut self) -> Option<(usize, usize, &'a BStr)> {
+        // This does not implement full UAX #29 grapheme cluster boundary
+        // rules (e.g. combining marks, ZWJ emoji sequences); it treats each
+        // codepoint as its own cluster, which is correct for the common
+        // case of non-combining text.
+        let (start, end, _) = self.chars.next()?;
+        Some((start, end, BStr::from_bytes(&self.bytes[start..end])))
+    }
+}
+
+/// An iterator over the words of a byte string, skipping non-word runs such
+/// as whitespace and punctuation.
+#[derive(Clone, Debug)]
+pub struct Words<'a> {
+    it: WordIndices<'a>,
+}
+
+impl<'a> Words<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Words<'a> {
+        Words { it: WordIndices::new(bytes) }
+    }
+}
+
+impl<'a> Iterator for Words<'a> {
+    type Item = &'a BStr;
+
+    fn next(&mut self) -> Option<&'a BStr> {
+        self.it.next().map(|(_, _, w)| w)
+    }
+}
+
+/// Like [`Words`](struct.Words.html), but also yields the byte range of each
+/// word.
+#[derive(Clone, Debug)]
+pub struct WordIndices<'a> {
+    it: WordsWithBreakIndices<'a>,
+}
+
+impl<'a> WordIndices<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> WordIndices<'a> {
+        WordIndices { it: WordsWithBreakIndices::new(bytes) }
+    }
+}
+
+impl<'a> Iterator for WordIndices<'a> {
+    type Item = (usize, usize, &'a BStr);
+
+    fn next(&mut self) -> Option<(usize, usize, &'a BStr)> {
+        loop {
+            let (start, end, word) = self.it.next()?;
+            if word.chars().next().map_or(false, is_word_byte) {
+                return Some((start, end, word));
+            }
+        }
+    }
+}
+
+/// An iterator over every run of the byte string, both word and non-word
+/// (e.g. whitespace, punctuation) breaks.
+#[derive(Clone, Debug)]
+pub struct WordsWithBreaks<'a> {
+    it: WordsWithBreakIndices<'a>,
+}
+
+impl<'a> WordsWithBreaks<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> WordsWithBreaks<'a> {
+        WordsWithBreaks { it: WordsWithBreakIndices::new(bytes) }
+    }
+}
+
+impl<'a> Iterator for WordsWithBreaks<'a> {
+    type Item = &'a BStr;
+
+    fn next(&mut self) -> Option<&'a BStr> {
+        self.it.next().map(|(_, _, w)| w)
+    }
+}
+
+/// Like [`WordsWithBreaks`](struct.WordsWithBreaks.html), but also yields
+/// the byte range of each run.
+#[derive(Clone, Debug)]
+pub struct WordsWithBreakIndices<'a> {
+    bytes: &'a [u8],
+    chars: CharIndices<'a>,
+}
+
+impl<'a> WordsWithBreakIndices<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> WordsWithBreakIndices<'a> {
+        WordsWithBreakIndices { bytes, chars: CharIndices::new(bytes) }
+    }
+}
+
+impl<'a> Iterator for WordsWithBreakIndices<'a> {
+    type Item = (usize, usize, &'a BStr);
+
+    fn next(&mut self) -> Option<(usize, usize, &'a BStr)> {
+        let (start, mut end, first) = self.chars.next()?;
+        let in_word = is_word_byte(first);
+        loop {
+            let mut peek = self.chars.clone();
+            match peek.next() {
+                Some((s, e, ch)) if is_word_byte(ch) == in_word => {
+                    end = e;
+                    self.chars = peek;
+                    let _ = s;
+                }
+                _ => break,
+            }
+        }
+        Some((start, end, BStr::from_bytes(&self.bytes[start..end])))
+    }
+}
+
+/// An iterator over the sentences of a byte string.
+#[derive(Clone, Debug)]
+pub struct Sentences<'a> {
+    it: SentenceIndices<'a>,
+}
+
+impl<'a> Sentences<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Sentences<'a> {
+        Sentences { it: SentenceIndices::new(bytes) }
+    }
+}
+
+impl<'a> Iterator for Sentences<'a> {
+    type Item = &'a BStr;
+
+    fn next(&mut self) -> Option<&'a BStr> {
+        self.it.next().map(|(_, _, s)| s)
+    }
+}
+
+/// Like [`Sentences`](struct.Sentences.html), but also yields the byte
+/// range of each sentence.
+#[derive(Clone, Debug)]
+pub struct SentenceIndices<'a> {
+    bytes: &'a [u8],
+    chars: CharIndices<'a>,
+}
+
+impl<'a> SentenceIndices<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> SentenceIndices<'a> {
+        SentenceIndices { bytes, chars: CharIndices::new(bytes) }
+    }
+}
+
+impl<'a> Iterator for SentenceIndices<'a> {
+    type Item = (usize, usize, &'a BStr);
+
+    fn next(&mut self) -> Option<(usize, usize, &'a BStr)> {
+        let (start, mut end, first) = self.chars.next()?;
+        let mut prev_ended_sentence = matches!(first, '.' | '!' | '?');
+        loop {
+            let mut peek = self.chars.clone();
+            match peek.next() {
+                None => break,
+                Some((_, e, ch)) => {
+                    if prev_ended_sentence && !ch.is_whitespace() {
+                        break;
+                    }
+                    end = e;
+                    prev_ended_sentence = prev_ended_sentence || matches!(ch, '.' | '!' | '?');
+                    self.chars = peek;
+                }
+            }
+        }
+        Some((start, end, BStr::from_bytes(&self.bytes[start..end])))
+    }
+}
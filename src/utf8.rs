@@ -0,0 +1,287 @@
+use core::fmt;
+
+use bstr::BStr;
+
+const TAG_CONT: u8 = 0b1000_0000;
+const TAG_TWO: u8 = 0b1100_0000;
+const TAG_THREE: u8 = 0b1110_0000;
+const TAG_FOUR: u8 = 0b1111_0000;
+
+/// The replacement codepoint substituted for invalid UTF-8.
+pub(crate) const REPLACEMENT: char = '\u{FFFD}';
+
+/// An error that occurs when decoding invalid UTF-8.
+///
+/// This error indicates that a valid Unicode codepoint could not be decoded
+/// from a particular location, and includes the number of bytes that make up
+/// the erroneous sequence (as determined by the "substitution of maximal
+/// subparts" strategy).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Utf8Error {
+    pub(crate) valid_up_to: usize,
+    pub(crate) error_len: Option<usize>,
+}
+
+impl Utf8Error {
+    /// Returns the index into the original byte string up to which valid
+    /// UTF-8 was found.
+    pub fn valid_up_to(&self) -> usize {
+        self.valid_up_to
+    }
+
+    /// Returns the length of the invalid byte sequence, if it could be
+    /// determined. When `None`, the sequence was incomplete (i.e. more bytes
+    /// may complete a valid codepoint).
+    pub fn error_len(&self) -> Option<usize> {
+        self.error_len
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn from_std(err: core::str::Utf8Error) -> Utf8Error {
+        Utf8Error { valid_up_to: err.valid_up_to(), error_len: err.error_len() }
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn at(valid_up_to: usize) -> Utf8Error {
+        Utf8Error { valid_up_to, error_len: Some(1) }
+    }
+}
+
+impl fmt::Display for Utf8Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid UTF-8 found at byte offset {}", self.valid_up_to)
+    }
+}
+
+#[inline]
+fn len_for_lead_byte(b: u8) -> usize {
+    if b < 0x80 {
+        1
+    } else if b & 0b1110_0000 == TAG_TWO {
+        2
+    } else if b & 0b1111_0000 == TAG_THREE {
+        3
+    } else if b & 0b1111_1000 == TAG_FOUR {
+        4
+    } else {
+        1
+    }
+}
+
+#[inline]
+fn is_continuation(b: u8) -> bool {
+    b & 0b1100_0000 == TAG_CONT
+}
+
+/// Decode the first UTF-8 codepoint at the start of `bytes`.
+///
+/// Returns the decoded `char` (or `None` if `bytes` is empty, or if the
+/// leading sequence is invalid) along with the number of bytes it occupies.
+/// When decoding fails, the returned size follows the "substitution of
+/// maximal subparts" strategy: it is the length of the longest prefix of
+/// `bytes` that begins some valid encoding (at least 1), which is all of
+/// `bytes` when the input ends partway through such a prefix.
+pub fn decode(bytes: &[u8]) -> (Option<char>, usize) {
+    if bytes.is_empty() {
+        return (None, 0);
+    }
+    let len = len_for_lead_byte(bytes[0]);
+    if len == 1 {
+        if bytes[0] < 0x80 {
+            return (Some(bytes[0] as char), 1);
+        }
+        return (None, 1);
+    }
+    // `error_len` is the length of the maximal subpart; `None` means the
+    // input ended partway through a valid prefix.
+    let end = core::cmp::min(len, bytes.len());
+    match core::str::from_utf8(&bytes[..end]) {
+        Ok(s) => (s.chars().next(), end),
+        Err(err) => (None, err.error_len().unwrap_or(end)),
+    }
+}
+
+/// Decode the last UTF-8 codepoint at the end of `bytes`.
+///
+/// This is the mirror image of [`decode`](fn.decode.html), used when
+/// scanning a byte string from the back.
+pub fn decode_last(bytes: &[u8]) -> (Option<char>, usize) {
+    if bytes.is_empty() {
+        return (None, 0);
+    }
+    let mut start = bytes.len() - 1;
+    // Walk back over continuation bytes, at most 3 of them.
+    let mut back = 0;
+    while back < 3 && start > 0 && is_continuation(bytes[start]) {
+        start -= 1;
+        back += 1;
+    }
+    let (ch, size) = decode(&bytes[start..]);
+    if size == bytes.len() - start {
+        (ch, size)
+    } else {
+        // The lead byte we found doesn't account for all of the trailing
+        // continuation bytes we consumed; treat just the final byte as
+        // invalid instead.
+        (None, 1)
+    }
+}
+
+/// An iterator over the Unicode codepoints in a byte string.
+///
+/// Invalid UTF-8 is substituted with `U+FFFD`, following the "substitution
+/// of maximal subparts" strategy.
+#[derive(Clone, Debug)]
+pub struct Chars<'a> {
+    bs: &'a [u8],
+}
+
+impl<'a> Chars<'a> {
+    pub(crate) fn new(bs: &'a [u8]) -> Chars<'a> {
+        Chars { bs }
+    }
+}
+
+impl<'a> Iterator for Chars<'a> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        if self.bs.is_empty() {
+            return None;
+        }
+        let (ch, size) = decode(self.bs);
+        self.bs = &self.bs[size..];
+        Some(ch.unwrap_or(REPLACEMENT))
+    }
+}
+
+/// Like [`Chars`](struct.Chars.html), but yields the byte range of each
+/// codepoint along with the codepoint itself.
+#[derive(Clone, Debug)]
+pub struct CharIndices<'a> {
+    bs: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> CharIndices<'a> {
+    pub(crate) fn new(bs: &'a [u8]) -> CharIndices<'a> {
+        CharIndices { bs, pos: 0 }
+    }
+}
+
+impl<'a> Iterator for CharIndices<'a> {
+    type Item = (usize, usize, char);
+
+    fn next(&mut self) -> Option<(usize, usize, char)> {
+        if self.bs.is_empty() {
+            return None;
+        }
+        let (ch, size) = decode(self.bs);
+        let start = self.pos;
+        let end = start + size;
+        self.bs = &self.bs[size..];
+        self.pos = end;
+        Some((start, end, ch.unwrap_or(REPLACEMENT)))
+    }
+}
+
+/// A chunk yielded by [`Utf8Chunks`](struct.Utf8Chunks.html): a maximal run
+/// of valid UTF-8, followed by the maximal run of invalid bytes after it.
+///
+/// Concatenating `valid()` and then `invalid()`, across every chunk in
+/// order, reconstructs the original byte string exactly.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Utf8Chunk<'a> {
+    valid: &'a str,
+    invalid: &'a [u8],
+}
+
+impl<'a> Utf8Chunk<'a> {
+    /// The longest valid UTF-8 prefix of this chunk.
+    ///
+    /// This is empty when the chunk begins with invalid bytes.
+    pub fn valid(&self) -> &'a str {
+        self.valid
+    }
+
+    /// The run of invalid bytes immediately following [`valid`](#method.valid).
+    ///
+    /// This is empty when the chunk ended only because the input was
+    /// exhausted, rather than because invalid bytes were found.
+    pub fn invalid(&self) -> &'a BStr {
+        BStr::from_bytes(self.invalid)
+    }
+}
+
+/// A lossless iterator over alternating runs of valid and invalid UTF-8.
+///
+/// Unlike [`Chars`](struct.Chars.html), which substitutes invalid UTF-8
+/// with `U+FFFD` and therefore can't round-trip the original bytes, each
+/// item here exposes both the valid prefix and the invalid bytes that
+/// follow it, so the original byte string can always be reconstructed
+/// exactly. This is the building block for transcoding-on-the-fly or
+/// substituting invalid sequences with a custom marker without losing any
+/// data, e.g. when re-emitting matched lines from a grep-like tool.
+///
+/// # Example
+///
+/// ```
+/// use bstr::B;
+///
+/// let bs = B(b"foo\xFFbar\xE2\x98baz");
+/// let chunks: Vec<(&str, &[u8])> = bs
+///     .utf8_chunks()
+///     .map(|chunk| (chunk.valid(), chunk.invalid().as_bytes()))
+///     .collect();
+/// assert_eq!(
+///     chunks,
+///     vec![("foo", &b"\xFF"[..]), ("bar", &b"\xE2\x98"[..]), ("baz", &b""[..])],
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct Utf8Chunks<'a> {
+    bs: &'a [u8],
+}
+
+impl<'a> Utf8Chunks<'a> {
+    pub(crate) fn new(bs: &'a [u8]) -> Utf8Chunks<'a> {
+        Utf8Chunks { bs }
+    }
+}
+
+impl<'a> Iterator for Utf8Chunks<'a> {
+    type Item = Utf8Chunk<'a>;
+
+    fn next(&mut self) -> Option<Utf8Chunk<'a>> {
+        if self.bs.is_empty() {
+            return None;
+        }
+
+        let mut valid_len = 0;
+        while valid_len < self.bs.len() {
+            let (ch, size) = decode(&self.bs[valid_len..]);
+            if ch.is_none() {
+                break;
+            }
+            valid_len += size;
+        }
+        // Every byte consumed above came from a successful decode of a
+        // complete codepoint, so this prefix is guaranteed to be valid
+        // UTF-8.
+        let valid = core::str::from_utf8(&self.bs[..valid_len])
+            .expect("decode() only advances over valid UTF-8");
+
+        let mut invalid_len = 0;
+        while valid_len + invalid_len < self.bs.len() {
+            let (ch, size) = decode(&self.bs[valid_len + invalid_len..]);
+            if ch.is_some() {
+                break;
+            }
+            invalid_len += size;
+        }
+
+        let invalid = &self.bs[valid_len..valid_len + invalid_len];
+        self.bs = &self.bs[valid_len + invalid_len..];
+        Some(Utf8Chunk { valid, invalid })
+    }
+}
@@ -0,0 +1,137 @@
+//! A minimal WTF-8 codec.
+//!
+//! This is used only on non-Unix platforms (chiefly Windows), where
+//! `OsString` is backed by a sequence of UTF-16 code units that is allowed
+//! to contain unpaired surrogates (legal in `OsString`, legal WTF-8, but
+//! illegal UTF-8). Going through WTF-8 rather than a strict UTF-8 check
+//! lets bytes round-trip through `OsString::encode_wide`/`from_wide`
+//! exactly, even when they contain such surrogates.
+//!
+//! `OsStr`'s own internal representation is private, and std exposes no
+//! way to borrow its bytes directly on this platform, so this only helps
+//! the *owning* conversions (`BString::from_os_string`/`into_os_string`)
+//! and the lossy `BStr::to_os_str_lossy`/`to_path_lossy`; the zero-copy
+//! `BStr::to_os_str`/`to_path` still require strict UTF-8.
+
+use utf8::Utf8Error;
+
+/// Encode a sequence of (possibly ill-formed) UTF-16 code units as WTF-8.
+///
+/// Surrogate pairs are combined into their supplementary codepoint and
+/// encoded as ordinary 4-byte UTF-8. Unpaired surrogates are encoded using
+/// the same 3-byte pattern UTF-8 uses for codepoints in that range, which
+/// is exactly what distinguishes WTF-8 from UTF-8. This never fails: every
+/// sequence of UTF-16 code units, well-formed or not, has a WTF-8 encoding.
+pub(crate) fn encode_wide(units: &[u16]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(units.len());
+    let mut iter = units.iter().copied().peekable();
+    while let Some(unit) = iter.next() {
+        if is_leading_surrogate(unit) {
+            if let Some(&next) = iter.peek() {
+                if is_trailing_surrogate(next) {
+                    iter.next();
+                    let c = 0x10000
+                        + (u32::from(unit) - 0xD800) * 0x400
+                        + (u32::from(next) - 0xDC00);
+                    push_utf8_4(&mut bytes, c);
+                    continue;
+                }
+            }
+            push_surrogate_3(&mut bytes, unit);
+        } else if is_trailing_surrogate(unit) {
+            push_surrogate_3(&mut bytes, unit);
+        } else {
+            let mut buf = [0u8; 4];
+            let s = char::from_u32(u32::from(unit)).unwrap_or('\u{FFFD}').encode_utf8(&mut buf);
+            bytes.extend_from_slice(s.as_bytes());
+        }
+    }
+    bytes
+}
+
+/// Decode WTF-8 bytes (ordinary UTF-8, plus the 3-byte surrogate form
+/// produced by [`encode_wide`](fn.encode_wide.html)) back into UTF-16 code
+/// units, re-pairing surrogates around supplementary codepoints exactly as
+/// `encode_wide` split them apart.
+pub(crate) fn decode_wide(bytes: &[u8]) -> Result<Vec<u16>, Utf8Error> {
+    let mut units = Vec::with_capacity(bytes.len());
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let b0 = bytes[pos];
+        if b0 < 0x80 {
+            units.push(u16::from(b0));
+            pos += 1;
+        } else if b0 & 0xE0 == 0xC0 {
+            let (c, len) = decode_cont(bytes, pos, 2, u32::from(b0 & 0x1F), 0x80)?;
+            units.push(c as u16);
+            pos += len;
+        } else if b0 & 0xF0 == 0xE0 {
+            let (c, len) = decode_cont(bytes, pos, 3, u32::from(b0 & 0x0F), 0x800)?;
+            units.push(c as u16);
+            pos += len;
+        } else if b0 & 0xF8 == 0xF0 {
+            let (c, len) = decode_cont(bytes, pos, 4, u32::from(b0 & 0x07), 0x10000)?;
+            if c > 0x10FFFF {
+                return Err(Utf8Error::at(pos));
+            }
+            let c = c - 0x10000;
+            units.push(0xD800 + (c >> 10) as u16);
+            units.push(0xDC00 + (c & 0x3FF) as u16);
+            pos += len;
+        } else {
+            return Err(Utf8Error::at(pos));
+        }
+    }
+    Ok(units)
+}
+
+#[inline]
+fn is_leading_surrogate(unit: u16) -> bool {
+    (0xD800..=0xDBFF).contains(&unit)
+}
+
+#[inline]
+fn is_trailing_surrogate(unit: u16) -> bool {
+    (0xDC00..=0xDFFF).contains(&unit)
+}
+
+fn push_surrogate_3(bytes: &mut Vec<u8>, surrogate: u16) {
+    let c = u32::from(surrogate);
+    bytes.push(0xE0 | (c >> 12) as u8);
+    bytes.push(0x80 | ((c >> 6) & 0x3F) as u8);
+    bytes.push(0x80 | (c & 0x3F) as u8);
+}
+
+fn push_utf8_4(bytes: &mut Vec<u8>, c: u32) {
+    bytes.push(0xF0 | (c >> 18) as u8);
+    bytes.push(0x80 | ((c >> 12) & 0x3F) as u8);
+    bytes.push(0x80 | ((c >> 6) & 0x3F) as u8);
+    bytes.push(0x80 | (c & 0x3F) as u8);
+}
+
+fn decode_cont(
+    bytes: &[u8],
+    pos: usize,
+    len: usize,
+    lead: u32,
+    min: u32,
+) -> Result<(u32, usize), Utf8Error> {
+    if pos + len > bytes.len() {
+        return Err(Utf8Error::at(pos));
+    }
+    let mut c = lead;
+    for &b in &bytes[pos + 1..pos + len] {
+        if b & 0xC0 != 0x80 {
+            return Err(Utf8Error::at(pos));
+        }
+        c = (c << 6) | u32::from(b & 0x3F);
+    }
+    // Reject overlong encodings: a codepoint that fits in fewer bytes must
+    // be rejected here rather than silently accepted (or, for the 4-byte
+    // case, underflowing the `c - 0x10000` surrogate-pair computation
+    // above).
+    if c < min {
+        return Err(Utf8Error::at(pos));
+    }
+    Ok((c, len))
+}